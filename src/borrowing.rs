@@ -0,0 +1,281 @@
+use crate::{default_features, feature_distance, Lexeme, Lexicon, Loan, Origin, Phoneme, PhoneticInventory, Sense, SenseSource, WordGenerator};
+use uuid::Uuid;
+
+// Digraphs a donor spelling may use even when the recipient has no such phoneme.
+const DONOR_DIGRAPHS: &[&str] = &["th", "dh", "sh", "zh", "ch", "ng", "kh", "gh"];
+const DONOR_VOWELS: &[char] = &['a', 'e', 'i', 'o', 'u', 'y'];
+
+// Costs of each repair when fitting a loan to the recipient's syllable rules; the cheapest
+// repair wins, so vowel epenthesis is preferred over consonant epenthesis over deletion.
+const INSERT_VOWEL: u32 = 2;
+const INSERT_CONSONANT: u32 = 3;
+const DELETE: u32 = 4;
+const MAX_ILLEGAL_REPAIRS: usize = 5;
+
+// One slot of a repaired syllable: a donor segment kept at this index, or an epenthetic phoneme.
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    Keep(usize),
+    Insert { vowel: bool },
+}
+
+// What a round of borrowing did: the new loanwords, and the donor words that could not be
+// fitted to this language's sounds.
+#[derive(Debug, Clone, Default)]
+pub struct Borrowing {
+    pub loans: Vec<Uuid>,
+    pub unadapted: Vec<Uuid>,
+}
+
+impl WordGenerator {
+    // Borrows every donor word whose core sense carries one of `domains` into `recipient`,
+    // adapted to this language's sounds and syllable rules. Idioms are never borrowed, nor is
+    // a donor word the recipient already has a loan of. A loan that sounds like a word the
+    // recipient already has follows the homophony policy, like a derivation; a loan has no
+    // allomorphs, so `RetryAllomorph` rejects it.
+    pub fn borrow_words(&self, inventory: &PhoneticInventory, recipient: &mut Lexicon, donor: &Lexicon, donor_language: &str, domains: &[String]) -> Borrowing {
+        let already_borrowed: Vec<Uuid> = recipient.graph.values()
+            .filter_map(|l| l.borrowed_from.as_ref())
+            .filter(|loan| loan.language == donor_language)
+            .map(|loan| loan.donor_id)
+            .collect();
+
+        let mut sources: Vec<&Lexeme> = donor.graph.values()
+            .filter(|l| l.origin != Origin::Idiom && !already_borrowed.contains(&l.id))
+            .filter(|l| l.tags().iter().any(|t| domains.contains(t)))
+            .collect();
+        sources.sort_by(|a, b| a.form.cmp(&b.form));
+
+        let mut form_to_ids = recipient.form_index();
+
+        let mut borrowing = Borrowing::default();
+        for source in sources {
+            let Some(form) = self.adapt_form(inventory, &source.form) else {
+                borrowing.unadapted.push(source.id);
+                continue;
+            };
+            if !self.admits_form(recipient, &form_to_ids, &form, None, format!("borrowing from {}", donor_language)) {
+                continue;
+            }
+
+            let loan = Lexeme {
+                id: Uuid::new_v4(),
+                form,
+                part_of_speech: source.part_of_speech.clone(),
+                senses: vec![Sense::new(source.meaning(), source.tags().to_vec(), SenseSource::Core)],
+                meaning_shifts: Vec::new(),
+                form_changes: Vec::new(),
+                parent_id: None,
+                rule_applied: None,
+                affix_applied: None,
+                origin: Origin::Loan,
+                borrowed_from: Some(Loan {
+                    language: donor_language.to_string(),
+                    donor_id: source.id,
+                    donor_form: source.form.clone(),
+                    donor_meaning: source.meaning().to_string(),
+                }),
+            };
+            borrowing.loans.push(loan.id);
+            form_to_ids.entry(loan.form.clone()).or_default().push(loan.id);
+            recipient.add_lexeme(loan);
+        }
+        borrowing
+    }
+
+    // Fits a foreign spelling to this language: each segment becomes the nearest native
+    // phoneme, the result is re-syllabified against the syllable rules (inserting or dropping
+    // segments as needed), and illegal patterns are broken up with an epenthetic vowel.
+    // Returns `None` when no legal form could be found.
+    pub fn adapt_form(&self, inventory: &PhoneticInventory, foreign: &str) -> Option<String> {
+        let mut segments: Vec<&Phoneme> = segment_foreign(inventory, foreign).into_iter()
+            .filter_map(|(grapheme, vowel)| nearest_phoneme(inventory, grapheme, vowel))
+            .collect();
+
+        for _ in 0..=MAX_ILLEGAL_REPAIRS {
+            let form = self.syllabify(inventory, &segments)?;
+            let Some(start) = self.illegal_patterns.iter().filter_map(|p| form.find(p.as_str())).min() else {
+                return Some(form);
+            };
+            // Split the offending cluster after its first phoneme.
+            let epenthetic = epenthetic_phoneme(inventory, true)?;
+            let native = inventory.segment(&form);
+            let mut offset = 0;
+            let split = native.iter().position(|p| {
+                offset += p.grapheme.len();
+                offset > start
+            })?;
+            segments = native;
+            segments.insert(split + 1, epenthetic);
+        }
+        None
+    }
+
+    // The cheapest sequence of syllables, each matching one of the syllable rules, that
+    // covers `segments`; see the repair costs above.
+    fn syllabify(&self, inventory: &PhoneticInventory, segments: &[&Phoneme]) -> Option<String> {
+        let patterns: Vec<Vec<bool>> = self.rules.iter()
+            .map(|r| r.pattern.chars().filter_map(|c| match c {
+                'C' => Some(false),
+                'V' => Some(true),
+                _ => None,
+            }).collect::<Vec<bool>>())
+            .filter(|p| !p.is_empty())
+            .collect();
+
+        // best[i]: cheapest repair covering the first i segments, with the position it came
+        // from and the slots of the syllable (empty for a deletion) that got it there.
+        let n = segments.len();
+        let mut best: Vec<Option<(u32, usize, Vec<Slot>)>> = vec![None; n + 1];
+        best[0] = Some((0, 0, Vec::new()));
+        for i in 0..n {
+            let Some((cost, _, _)) = best[i] else { continue };
+            let mut relax = |j: usize, new_cost: u32, slots: Vec<Slot>| {
+                if best[j].as_ref().is_none_or(|(c, _, _)| new_cost < *c) {
+                    best[j] = Some((new_cost, i, slots));
+                }
+            };
+            relax(i + 1, cost + DELETE, Vec::new());
+
+            for pattern in &patterns {
+                // Every way of filling the pattern: bit k set = slot k is epenthetic.
+                'fill: for mask in 0..(1u32 << pattern.len()) {
+                    let mut j = i;
+                    let mut extra = 0;
+                    let mut slots = Vec::new();
+                    for (k, &vowel) in pattern.iter().enumerate() {
+                        if mask & (1 << k) != 0 {
+                            extra += if vowel { INSERT_VOWEL } else { INSERT_CONSONANT };
+                            slots.push(Slot::Insert { vowel });
+                        } else if j < n && segments[j].is_vowel() == vowel {
+                            slots.push(Slot::Keep(j));
+                            j += 1;
+                        } else {
+                            continue 'fill;
+                        }
+                    }
+                    if j > i {
+                        relax(j, cost + extra, slots);
+                    }
+                }
+            }
+        }
+
+        let mut syllables = Vec::new();
+        let mut position = n;
+        while position > 0 {
+            let (_, previous, slots) = best[position].as_ref()?;
+            if !slots.is_empty() {
+                syllables.push(slots.clone());
+            }
+            position = *previous;
+        }
+        if syllables.is_empty() {
+            return None;
+        }
+
+        let mut form = String::new();
+        for slot in syllables.iter().rev().flatten() {
+            let phoneme = match *slot {
+                Slot::Keep(index) => segments[index],
+                Slot::Insert { vowel } => epenthetic_phoneme(inventory, vowel)?,
+            };
+            form.push_str(&phoneme.grapheme);
+        }
+        Some(form)
+    }
+}
+
+// Splits a foreign spelling into (grapheme, is vowel) pairs, preferring the recipient's own
+// graphemes and known digraphs, longest first. Hyphens and other non-letters are dropped.
+fn segment_foreign<'a>(inventory: &PhoneticInventory, foreign: &'a str) -> Vec<(&'a str, bool)> {
+    let mut segments = Vec::new();
+    let mut rest = foreign;
+    while let Some(c) = rest.chars().next() {
+        if !c.is_alphabetic() {
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let native = inventory.phonemes()
+            .filter(|p| !p.grapheme.is_empty() && rest.starts_with(p.grapheme.as_str()))
+            .max_by_key(|p| p.grapheme.len());
+        let digraph = DONOR_DIGRAPHS.iter().find(|d| rest.starts_with(**d));
+        let (len, vowel) = match (native, digraph) {
+            (Some(p), Some(d)) if d.len() > p.grapheme.len() => (d.len(), false),
+            (Some(p), _) => (p.grapheme.len(), p.is_vowel()),
+            (None, Some(d)) => (d.len(), false),
+            (None, None) => (c.len_utf8(), DONOR_VOWELS.contains(&c)),
+        };
+        segments.push((&rest[..len], vowel));
+        rest = &rest[len..];
+    }
+    segments
+}
+
+// The native phoneme of the same class sharing the most features with `grapheme`; an exact
+// grapheme match always wins.
+fn nearest_phoneme<'a>(inventory: &'a PhoneticInventory, grapheme: &str, vowel: bool) -> Option<&'a Phoneme> {
+    let candidates = if vowel { &inventory.vowels } else { &inventory.consonants };
+    if let Some(exact) = candidates.iter().find(|p| p.grapheme == grapheme) {
+        return Some(exact);
+    }
+    closest_to(candidates, default_features(grapheme, vowel))
+}
+
+// The sound inserted to repair a syllable: a mid central vowel, or a glottal consonant,
+// or whatever this language has closest to them.
+fn epenthetic_phoneme(inventory: &PhoneticInventory, vowel: bool) -> Option<&Phoneme> {
+    if vowel {
+        closest_to(&inventory.vowels, &["mid", "central"])
+    } else {
+        closest_to(&inventory.consonants, &["glottal", "fricative", "voiceless"])
+    }
+}
+
+// The first phoneme with the fewest features differing from `target`.
+fn closest_to<'a>(candidates: &'a [Phoneme], target: &[&str]) -> Option<&'a Phoneme> {
+    candidates.iter().min_by_key(|p| feature_distance(&p.features(), target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{generator, inventory, root};
+    use crate::CollisionResolution;
+    use serde_json::json;
+
+    #[test]
+    fn loans_are_not_listed_as_native_roots() {
+        let mut donor = Lexicon::new();
+        donor.add_lexeme(root("tukad", "noun", "coin", &["trade"]));
+        let mut recipient = Lexicon::new();
+        let native = root("gora", "noun", "stone", &[]);
+        let native_id = native.id;
+        recipient.add_lexeme(native);
+
+        let borrowing = generator(json!({})).borrow_words(&inventory(), &mut recipient, &donor, "Donor", &["trade".to_string()]);
+        assert_eq!(borrowing.loans.len(), 1);
+        assert_eq!(recipient.graph[&borrowing.loans[0]].form, "tukad");
+        assert_eq!(recipient.roots, [native_id]);
+    }
+
+    #[test]
+    fn a_loan_that_sounds_like_a_native_word_follows_the_homophony_policy() {
+        let mut donor = Lexicon::new();
+        donor.add_lexeme(root("gora", "noun", "coin", &["trade"]));
+        let domains = ["trade".to_string()];
+
+        let mut recipient = Lexicon::new();
+        recipient.add_lexeme(root("gora", "noun", "stone", &[]));
+        let rejected = generator(json!({})).borrow_words(&inventory(), &mut recipient, &donor, "Donor", &domains);
+        assert!(rejected.loans.is_empty());
+        assert_eq!(recipient.collisions[0].resolution, CollisionResolution::Rejected);
+
+        let mut recipient = Lexicon::new();
+        recipient.add_lexeme(root("gora", "noun", "stone", &[]));
+        let allowed = generator(json!({ "morphology": { "homophony": "Allow" } }))
+            .borrow_words(&inventory(), &mut recipient, &donor, "Donor", &domains);
+        assert_eq!(allowed.loans.len(), 1);
+        assert_eq!(recipient.collisions[0].resolution, CollisionResolution::HomophoneAdded);
+    }
+}
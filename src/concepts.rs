@@ -0,0 +1,83 @@
+use crate::{Lexicon, Meaning, WordGenerator};
+
+impl WordGenerator {
+    // Every concept the language wants a word for, as (part of speech, meaning) pairs.
+    // Only parts of speech listed in `parts_of_speech` count (all of them if that list is empty),
+    // in a stable order so the same config always yields the same list.
+    pub fn concepts(&self) -> Vec<(String, &Meaning)> {
+        let generation = &self.lexicon_generation;
+        let mut parts_of_speech: Vec<&String> = if generation.parts_of_speech.is_empty() {
+            generation.meanings.keys().collect()
+        } else {
+            generation.parts_of_speech.iter().collect()
+        };
+        parts_of_speech.sort();
+        parts_of_speech.dedup();
+
+        parts_of_speech.into_iter()
+            .filter_map(|pos| generation.meanings.get(pos).map(|meanings| (pos, meanings)))
+            .flat_map(|(pos, meanings)| meanings.iter().map(move |m| (pos.clone(), m)))
+            .collect()
+    }
+
+    // How many roots it takes to give every concept exactly one word.
+    pub fn concept_count(&self) -> usize {
+        self.concepts().len()
+    }
+
+    // Concepts that no root in the lexicon was coined for.
+    pub fn uncovered_concepts(&self, lexicon: &Lexicon) -> Vec<(String, &Meaning)> {
+        self.concepts().into_iter()
+            .filter(|(pos, meaning)| {
+                !lexicon.roots.iter()
+                    .filter_map(|id| lexicon.graph.get(id))
+                    .any(|root| &root.part_of_speech == pos && root.meaning() == meaning.gloss)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{generator, inventory};
+    use crate::WordGenerator;
+    use serde_json::json;
+
+    fn unique_generator(allow_synonyms: bool) -> WordGenerator {
+        generator(json!({ "lexicon_generation": {
+            "parts_of_speech": ["noun", "verb"],
+            "meanings": { "noun": ["water", "stone"], "verb": ["drink"], "adj": ["red"] },
+            "assignment": "Unique",
+            "allow_synonyms": allow_synonyms,
+        } }))
+    }
+
+    #[test]
+    fn unique_assignment_gives_each_concept_one_root_and_then_stops() {
+        let generator = unique_generator(false);
+        assert_eq!(generator.concept_count(), 3);
+
+        let lexicon = generator.build_etymological_graph(10, &inventory(), 0);
+        let mut glosses: Vec<&str> = lexicon.roots.iter().map(|id| lexicon.graph[id].meaning()).collect();
+        glosses.sort();
+        assert_eq!(glosses, ["drink", "stone", "water"]);
+        assert!(generator.uncovered_concepts(&lexicon).is_empty());
+    }
+
+    #[test]
+    fn synonyms_are_allowed_only_once_every_concept_has_a_root() {
+        let generator = unique_generator(true);
+        let lexicon = generator.build_etymological_graph(6, &inventory(), 0);
+        let count = |gloss: &str| lexicon.roots.iter().filter(|id| lexicon.graph[id].meaning() == gloss).count();
+        assert_eq!((count("water"), count("stone"), count("drink")), (2, 2, 2));
+    }
+
+    #[test]
+    fn uncovered_concepts_lists_the_concepts_left_without_a_root() {
+        let generator = unique_generator(false);
+        let lexicon = generator.build_etymological_graph(2, &inventory(), 0);
+        let uncovered = generator.uncovered_concepts(&lexicon);
+        assert_eq!(uncovered.len(), 1);
+        assert!(!lexicon.roots.iter().any(|id| lexicon.graph[id].meaning() == uncovered[0].1.gloss));
+    }
+}
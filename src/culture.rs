@@ -0,0 +1,146 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// A description of the people who speak the language. The engine turns it into a salience
+// weight per semantic domain (a domain is just a meaning tag, like "metal" or "sea") and
+// uses that to decide how many roots and derivations each domain receives.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct Culture {
+    #[serde(default)]
+    pub environment: Vec<String>, // e.g. ["mountain"], ["coast", "forest"]
+    #[serde(default)]
+    pub values: Vec<String>,      // e.g. ["honour", "craft"]
+    #[serde(default)]
+    pub technology: Option<TechnologyLevel>,
+    #[serde(default)]
+    pub domain_weights: HashMap<String, f64>, // Explicit overrides, applied last.
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum TechnologyLevel {
+    Stone,
+    Bronze,
+    Iron,
+    Medieval,
+    Renaissance,
+}
+
+// How much each environment enriches which domains.
+const ENVIRONMENTS: &[(&str, &[(&str, f64)])] = &[
+    ("mountain", &[("mountain", 3.0), ("material", 2.0), ("metal", 2.0), ("weather", 1.5)]),
+    ("coast", &[("sea", 3.0), ("water", 2.0), ("weather", 1.5), ("trade", 1.5)]),
+    ("desert", &[("desert", 3.0), ("water", 2.0), ("sky", 1.5), ("weather", 1.5)]),
+    ("forest", &[("plant", 3.0), ("animal", 2.0)]),
+    ("river", &[("water", 2.5), ("trade", 1.5), ("plant", 1.5)]),
+    ("plains", &[("animal", 2.0), ("plant", 1.5), ("weather", 1.5)]),
+    ("tundra", &[("weather", 3.0), ("animal", 1.5)]),
+];
+
+// Domains tied to one kind of landscape; a culture that lives nowhere near them cares less.
+const LANDSCAPE_DOMAINS: &[&str] = &["mountain", "sea", "desert", "plant"];
+const IRRELEVANT_WEIGHT: f64 = 0.4;
+
+const VALUES: &[(&str, &[(&str, f64)])] = &[
+    ("honour", &[("honour", 3.0), ("war", 1.5), ("emotion", 1.5)]),
+    ("trade", &[("trade", 3.0), ("metal", 1.5)]),
+    ("piety", &[("religion", 3.0), ("death", 1.5)]),
+    ("war", &[("war", 3.0), ("violence", 2.0)]),
+    ("kinship", &[("kinship", 3.0), ("society", 1.5)]),
+    ("craft", &[("craft", 3.0), ("tool", 2.0), ("material", 1.5)]),
+    ("learning", &[("mental", 2.5), ("speech", 2.0)]),
+];
+
+impl TechnologyLevel {
+    fn weights(self) -> &'static [(&'static str, f64)] {
+        match self {
+            TechnologyLevel::Stone => &[("metal", 0.3), ("material", 1.5)],
+            TechnologyLevel::Bronze => &[("metal", 1.2), ("craft", 1.2)],
+            TechnologyLevel::Iron => &[("metal", 1.5), ("craft", 1.5), ("tool", 1.5)],
+            TechnologyLevel::Medieval => &[("metal", 1.5), ("craft", 1.5), ("building", 1.5), ("society", 1.5)],
+            TechnologyLevel::Renaissance => &[("craft", 1.5), ("trade", 1.5), ("mental", 1.5), ("building", 1.5)],
+        }
+    }
+}
+
+pub fn known_environments() -> impl Iterator<Item = &'static str> {
+    ENVIRONMENTS.iter().map(|(name, _)| *name)
+}
+
+pub fn known_values() -> impl Iterator<Item = &'static str> {
+    VALUES.iter().map(|(name, _)| *name)
+}
+
+// The resolved salience of every domain a culture has an opinion about.
+// Domains not listed have a neutral salience of 1.0.
+#[derive(Debug, Clone, Default)]
+pub struct DomainSalience {
+    weights: HashMap<String, f64>,
+}
+
+impl Culture {
+    pub fn domain_salience(&self) -> DomainSalience {
+        let mut weights: HashMap<String, f64> = HashMap::new();
+        // Enrichments from different sources don't stack; the strongest one wins.
+        let mut boost = |domain: &str, weight: f64| {
+            let entry = weights.entry(domain.to_string()).or_insert(1.0);
+            *entry = if weight < 1.0 { entry.min(weight) } else { entry.max(weight) };
+        };
+
+        let mut boosted_landscapes = Vec::new();
+        for environment in &self.environment {
+            if let Some((_, domains)) = ENVIRONMENTS.iter().find(|(name, _)| name == environment) {
+                for (domain, weight) in domains.iter() {
+                    boost(domain, *weight);
+                    boosted_landscapes.push(*domain);
+                }
+            }
+        }
+        if !self.environment.is_empty() {
+            for domain in LANDSCAPE_DOMAINS.iter().filter(|d| !boosted_landscapes.contains(d)) {
+                boost(domain, IRRELEVANT_WEIGHT);
+            }
+        }
+
+        for value in &self.values {
+            if let Some((_, domains)) = VALUES.iter().find(|(name, _)| name == value) {
+                for (domain, weight) in domains.iter() {
+                    boost(domain, *weight);
+                }
+            }
+        }
+
+        if let Some(technology) = self.technology {
+            for (domain, weight) in technology.weights() {
+                boost(domain, *weight);
+            }
+        }
+
+        for (domain, weight) in &self.domain_weights {
+            weights.insert(domain.clone(), weight.max(0.0));
+        }
+
+        DomainSalience { weights }
+    }
+}
+
+impl DomainSalience {
+    pub fn of_domain(&self, domain: &str) -> f64 {
+        self.weights.get(domain).copied().unwrap_or(1.0)
+    }
+
+    // The salience of something carrying these tags: its most salient domain if any is
+    // enriched, otherwise its least salient one (so "sea" words stay rare for mountain folk).
+    pub fn of_tags(&self, tags: &[String]) -> f64 {
+        let weights: Vec<f64> = tags.iter().map(|t| self.of_domain(t)).collect();
+        let highest = weights.iter().copied().fold(1.0, f64::max);
+        if highest > 1.0 {
+            highest
+        } else {
+            weights.iter().copied().fold(1.0, f64::min)
+        }
+    }
+
+    pub fn is_salient(&self, domain: &str) -> bool {
+        self.of_domain(domain) > 1.0
+    }
+}
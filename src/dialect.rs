@@ -0,0 +1,480 @@
+use crate::{ConfigError, DerivationProcess, FormChange, Lexicon, Meaning, Origin, PhoneticInventory, SoundChange, WordGenerator};
+use rand::prelude::*;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+// How a language splits into dialects: where its speech communities live and how often
+// innovations arise and travel between them. Communities come from the `map` file when
+// given, else from `communities`/`links`, otherwise from `grid`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DialectSettings {
+    #[serde(default = "default_steps")]
+    pub steps: usize,
+    #[serde(default = "default_innovation_rate")]
+    pub innovation_rate: f64,    // Chance per step that some community innovates.
+    #[serde(default = "default_sound_change_share")]
+    pub sound_change_share: f64, // Share of innovations that are sound changes; the rest replace a word.
+    #[serde(default = "default_spread_range")]
+    pub spread_range: f64,       // Travel cost at which the chance of adopting an innovation falls to 1/e.
+    #[serde(default)]
+    pub grid: GridSpec,
+    #[serde(default)]
+    pub communities: Vec<CommunitySpec>,
+    #[serde(default)]
+    pub links: Vec<LinkSpec>,
+    #[serde(default)]
+    pub map: Option<String>, // A GeoJSON or CSV map, relative to the config file; see map_import.
+    #[serde(default)]
+    pub terrain_costs: HashMap<String, f64>, // Overrides of the built-in terrain costs.
+}
+
+// A width x height grid of communities, each linked to its four neighbours at distance 1.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GridSpec {
+    #[serde(default = "default_grid_side")]
+    pub width: usize,
+    #[serde(default = "default_grid_side")]
+    pub height: usize,
+    #[serde(default)]
+    pub barriers: Vec<Barrier>,
+}
+
+// Rough terrain (a mountain range, a marsh) in one grid cell. Every link into or out of
+// the cell costs half of `cost` extra.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Barrier {
+    pub x: usize,
+    pub y: usize,
+    #[serde(default = "default_barrier_cost")]
+    pub cost: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommunitySpec {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+// A route between two named communities. Its cost is their distance plus `barrier`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LinkSpec {
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub barrier: f64,
+}
+
+fn default_steps() -> usize { 40 }
+fn default_innovation_rate() -> f64 { 0.6 }
+fn default_sound_change_share() -> f64 { 0.5 }
+fn default_spread_range() -> f64 { 1.5 }
+fn default_grid_side() -> usize { 3 }
+fn default_barrier_cost() -> f64 { 4.0 }
+
+const MAX_COINING_ATTEMPTS: usize = 100; // Tries at a replacement form the origin has no word for.
+
+impl Default for DialectSettings {
+    fn default() -> Self {
+        Self {
+            steps: default_steps(),
+            innovation_rate: default_innovation_rate(),
+            sound_change_share: default_sound_change_share(),
+            spread_range: default_spread_range(),
+            grid: GridSpec::default(),
+            communities: Vec::new(),
+            links: Vec::new(),
+            map: None,
+            terrain_costs: HashMap::new(),
+        }
+    }
+}
+
+impl Default for GridSpec {
+    fn default() -> Self {
+        Self { width: default_grid_side(), height: default_grid_side(), barriers: Vec::new() }
+    }
+}
+
+impl DialectSettings {
+    pub fn community_graph(&self) -> Result<CommunityGraph, ConfigError> {
+        if let Some(path) = &self.map {
+            CommunityGraph::import(path, &self.terrain_costs)
+        } else if self.communities.is_empty() {
+            Ok(CommunityGraph::grid(&self.grid))
+        } else {
+            CommunityGraph::from_specs(&self.communities, &self.links)
+        }
+    }
+}
+
+// Speech communities and the routes between them, weighted by how hard they are to travel.
+#[derive(Debug, Clone, Default)]
+pub struct CommunityGraph {
+    pub communities: Vec<Community>,
+    pub links: Vec<Link>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Community {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+// An undirected route between communities `a` and `b` (indices into `communities`).
+#[derive(Debug, Clone)]
+pub struct Link {
+    pub a: usize,
+    pub b: usize,
+    pub cost: f64,
+}
+
+impl CommunityGraph {
+    pub fn grid(spec: &GridSpec) -> Self {
+        let mut graph = Self::default();
+        let index = |x: usize, y: usize| y * spec.width + x;
+        let extra = |x: usize, y: usize| -> f64 {
+            spec.barriers.iter().filter(|b| b.x == x && b.y == y).map(|b| b.cost).sum()
+        };
+
+        for y in 0..spec.height {
+            for x in 0..spec.width {
+                graph.communities.push(Community { name: format!("{},{}", x, y), x: x as f64, y: y as f64 });
+            }
+        }
+        for y in 0..spec.height {
+            for x in 0..spec.width {
+                if x + 1 < spec.width {
+                    let cost = 1.0 + (extra(x, y) + extra(x + 1, y)) / 2.0;
+                    graph.links.push(Link { a: index(x, y), b: index(x + 1, y), cost });
+                }
+                if y + 1 < spec.height {
+                    let cost = 1.0 + (extra(x, y) + extra(x, y + 1)) / 2.0;
+                    graph.links.push(Link { a: index(x, y), b: index(x, y + 1), cost });
+                }
+            }
+        }
+        graph
+    }
+
+    pub fn from_specs(communities: &[CommunitySpec], links: &[LinkSpec]) -> Result<Self, ConfigError> {
+        let mut graph = Self {
+            communities: communities.iter()
+                .map(|c| Community { name: c.name.clone(), x: c.x, y: c.y })
+                .collect(),
+            links: Vec::new(),
+        };
+        for link in links {
+            let a = graph.index_of(&link.from).ok_or_else(|| ConfigError::UnknownCommunity(link.from.clone()))?;
+            let b = graph.index_of(&link.to).ok_or_else(|| ConfigError::UnknownCommunity(link.to.clone()))?;
+            let cost = graph.distance(a, b) + link.barrier.max(0.0);
+            graph.links.push(Link { a, b, cost });
+        }
+        Ok(graph)
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.communities.iter().position(|c| c.name == name)
+    }
+
+    // Straight-line distance between two communities.
+    pub fn distance(&self, a: usize, b: usize) -> f64 {
+        let (a, b) = (&self.communities[a], &self.communities[b]);
+        ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+    }
+
+    // The cheapest travel cost from `origin` to every community (infinite if unreachable).
+    pub fn travel_costs(&self, origin: usize) -> Vec<f64> {
+        let n = self.communities.len();
+        let mut costs = vec![f64::INFINITY; n];
+        let mut done = vec![false; n];
+        if origin < n {
+            costs[origin] = 0.0;
+        }
+        while let Some(current) = (0..n).filter(|&i| !done[i] && costs[i].is_finite())
+            .min_by(|&a, &b| costs[a].total_cmp(&costs[b]))
+        {
+            done[current] = true;
+            for link in self.links.iter().filter(|l| l.a == current || l.b == current) {
+                let next = if link.a == current { link.b } else { link.a };
+                costs[next] = costs[next].min(costs[current] + link.cost);
+            }
+        }
+        costs
+    }
+}
+
+// The result of a dialect simulation: one lexicon per community (same order as the graph's
+// communities) and every innovation with the communities that took it up.
+#[derive(Clone)]
+pub struct DialectSimulation {
+    pub graph: CommunityGraph,
+    pub lexicons: Vec<Lexicon>,
+    pub innovations: Vec<Innovation>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Innovation {
+    pub step: usize,
+    pub origin: usize,
+    pub kind: InnovationKind,
+    pub adopters: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub enum InnovationKind {
+    SoundChange(SoundChange),
+    // A root replaced by a newly coined word for the same meaning, with each adopting
+    // community's own form of the old word.
+    LexicalReplacement { root_id: Uuid, meaning: String, new_form: String, old_forms: Vec<(usize, String)> },
+}
+
+impl InnovationKind {
+    pub fn describe(&self) -> String {
+        match self {
+            InnovationKind::SoundChange(change) => format!("sound change {}", change.describe()),
+            InnovationKind::LexicalReplacement { meaning, new_form, old_forms, .. } => {
+                let mut was: Vec<&str> = old_forms.iter().map(|(_, form)| form.as_str()).collect();
+                was.sort();
+                was.dedup();
+                format!("'{}' is now {} (was {})", meaning, new_form, was.join(", "))
+            }
+        }
+    }
+}
+
+// Where an innovation stopped: the links with an adopter on one side only.
+#[derive(Debug, Clone)]
+pub struct Isogloss {
+    pub innovation: usize, // Index into `DialectSimulation::innovations`.
+    pub boundary: Vec<(usize, usize)>, // (adopter, non-adopter) pairs of communities.
+}
+
+impl DialectSimulation {
+    pub fn isoglosses(&self) -> Vec<Isogloss> {
+        self.innovations.iter().enumerate()
+            .map(|(i, innovation)| Isogloss {
+                innovation: i,
+                boundary: self.graph.links.iter()
+                    .filter_map(|l| match (innovation.adopters.contains(&l.a), innovation.adopters.contains(&l.b)) {
+                        (true, false) => Some((l.a, l.b)),
+                        (false, true) => Some((l.b, l.a)),
+                        _ => None,
+                    })
+                    .collect(),
+            })
+            .filter(|isogloss| !isogloss.boundary.is_empty())
+            .collect()
+    }
+
+    // How many innovations one community took up and the other did not.
+    pub fn divergence(&self, a: usize, b: usize) -> usize {
+        self.innovations.iter()
+            .filter(|i| i.adopters.contains(&a) != i.adopters.contains(&b))
+            .count()
+    }
+}
+
+impl WordGenerator {
+    // Gives every community a copy of `proto`, then for `steps` steps lets innovations arise
+    // in random communities and spread outwards as waves, each community adopting with a
+    // chance that decays with its travel cost from the origin.
+    pub fn simulate_dialects(&self, proto: &Lexicon, inventory: &PhoneticInventory, graph: &CommunityGraph) -> DialectSimulation {
+        let settings = &self.dialects;
+        let mut rng = rand::rng();
+        let mut simulation = DialectSimulation {
+            graph: graph.clone(),
+            lexicons: vec![proto.clone(); graph.communities.len()],
+            innovations: Vec::new(),
+        };
+        if graph.communities.is_empty() {
+            return simulation;
+        }
+
+        let mut root_ids: Vec<Uuid> = proto.roots.iter()
+            .filter(|id| proto.graph.get(*id).is_some_and(|l| l.origin == Origin::Root))
+            .copied()
+            .collect();
+        root_ids.sort();
+
+        for step in 1..=settings.steps {
+            if !rng.random_bool(settings.innovation_rate.clamp(0.0, 1.0)) {
+                continue;
+            }
+            let origin = rng.random_range(0..graph.communities.len());
+
+            let mut kind = if rng.random_bool(settings.sound_change_share.clamp(0.0, 1.0)) {
+                match SoundChange::random(inventory) {
+                    Some(change) => InnovationKind::SoundChange(change),
+                    None => continue,
+                }
+            } else {
+                let Some(root_id) = root_ids.choose(&mut rng).copied() else { continue };
+                let origin_lexicon = &simulation.lexicons[origin];
+                let Some(root) = origin_lexicon.graph.get(&root_id) else { continue };
+                let meaning = Meaning { gloss: root.meaning().to_string(), tags: root.tags().to_vec() };
+                let forms = origin_lexicon.form_index();
+                let Some(new_form) = (0..MAX_COINING_ATTEMPTS)
+                    .map(|_| self.generate_root_for(inventory, Some(&meaning)))
+                    .find(|form| !forms.contains_key(form))
+                else {
+                    continue;
+                };
+                InnovationKind::LexicalReplacement { root_id, meaning: meaning.gloss, new_form, old_forms: Vec::new() }
+            };
+
+            // The wave moves outwards from the origin; a community can only take the innovation
+            // up from a neighbour that already has it.
+            let costs = graph.travel_costs(origin);
+            let range = settings.spread_range.max(f64::EPSILON);
+            let mut order: Vec<usize> = (0..graph.communities.len()).filter(|&c| costs[c].is_finite()).collect();
+            order.sort_by(|&a, &b| costs[a].total_cmp(&costs[b]));
+            let mut adopters = vec![origin];
+            for community in order.into_iter().filter(|&c| c != origin) {
+                let reached = graph.links.iter().any(|l| {
+                    (l.a == community && adopters.contains(&l.b)) || (l.b == community && adopters.contains(&l.a))
+                });
+                if reached && rng.random_bool((-costs[community] / range).exp()) {
+                    adopters.push(community);
+                }
+            }
+            adopters.sort();
+
+            adopters.retain(|&community| {
+                let lexicon = &mut simulation.lexicons[community];
+                match &mut kind {
+                    InnovationKind::SoundChange(change) => {
+                        lexicon.apply_sound_change(inventory, change);
+                        true
+                    }
+                    InnovationKind::LexicalReplacement { root_id, meaning, new_form, old_forms } => {
+                        match self.replace_root(lexicon, *root_id, new_form, meaning) {
+                            Some(old_form) => {
+                                old_forms.push((community, old_form));
+                                true
+                            }
+                            None => false,
+                        }
+                    }
+                }
+            });
+
+            simulation.innovations.push(Innovation { step, origin, kind, adopters });
+        }
+        simulation
+    }
+    // Gives a root the newly coined `new_form` in one community's lexicon and rebuilds every
+    // word derived from it on the new stem, each recording why its form changed. Returns the
+    // root's old form, or `None` when the new form sounds like a word the community already
+    // has and the homophony policy refuses it.
+    fn replace_root(&self, lexicon: &mut Lexicon, root_id: Uuid, new_form: &str, meaning: &str) -> Option<String> {
+        let forms = lexicon.form_index();
+        if !self.admits_form(lexicon, &forms, new_form, None, format!("replacement of '{}'", meaning)) {
+            return None;
+        }
+        let root = lexicon.graph.get_mut(&root_id)?;
+        let old_form = std::mem::replace(&mut root.form, new_form.to_string());
+        root.form_changes.push((FormChange::Replaced, old_form.clone()));
+
+        // Descendants come generation by generation, so a parent is always rebuilt first.
+        let mut rebuilt = HashSet::from([root_id]);
+        let descendants: Vec<Uuid> = lexicon.descendants(&root_id).iter().map(|l| l.id).collect();
+        for id in descendants {
+            let lexeme = &lexicon.graph[&id];
+            let Some(parent_id) = lexeme.parent_id.filter(|p| rebuilt.contains(p)) else { continue };
+            let stem = &lexicon.graph[&parent_id].form;
+            let form = match lexeme.origin {
+                Origin::Derivation => {
+                    let rule = lexeme.rule_applied.as_ref()
+                        .and_then(|name| self.morphology.derivational_rules.iter().find(|r| &r.name == name));
+                    let (Some(rule), Some(affix)) = (rule, &lexeme.affix_applied) else { continue };
+                    match rule.process {
+                        DerivationProcess::Prefix { .. } => format!("{}{}", affix, stem),
+                        DerivationProcess::Suffix { .. } => format!("{}{}", stem, affix),
+                    }
+                }
+                Origin::Root | Origin::Idiom | Origin::Loan => continue,
+            };
+            if form == lexeme.form {
+                continue;
+            }
+            let lexeme = lexicon.graph.get_mut(&id)?;
+            let old = std::mem::replace(&mut lexeme.form, form);
+            lexeme.form_changes.push((FormChange::Restemmed, old));
+            rebuilt.insert(id);
+        }
+
+        // Idioms are made of plain roots, so the root may be the verb or the noun of one.
+        for lexeme in lexicon.graph.values_mut().filter(|l| l.origin == Origin::Idiom) {
+            if lexeme.form.split(' ').any(|word| word == old_form) {
+                let form = lexeme.form.split(' ')
+                    .map(|word| if word == old_form { new_form } else { word })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let old = std::mem::replace(&mut lexeme.form, form);
+                lexeme.form_changes.push((FormChange::Restemmed, old));
+            }
+        }
+        Some(old_form)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{derived, generator, inventory, root};
+    use crate::{CollisionResolution, Lexeme, SoundEnvironment};
+    use serde_json::json;
+
+    fn place_generator() -> WordGenerator {
+        generator(json!({ "morphology": { "derivational_rules": [{
+            "name": "Place",
+            "type": "Suffix",
+            "form": "-ko",
+            "applies_to_pos": ["noun"],
+            "output_pos": "noun",
+            "meaning_template": "place of {parent_meaning}",
+        }] } }))
+    }
+
+    #[test]
+    fn replacing_a_root_rebuilds_its_derived_words_and_idioms() {
+        let mut lexicon = Lexicon::new();
+        let water = root("kado", "noun", "water", &[]);
+        let well = derived(&water, "kado-ko", "place of water", "Place", "-ko");
+        let spring = derived(&well, "kado-ko-ko", "place of place of water", "Place", "-ko");
+        let drink = root("rad", "verb", "drink", &[]);
+        let idiom = Lexeme { origin: Origin::Idiom, parent_id: Some(drink.id), ..root("rad kado", "idiom", "to drink the water", &[]) };
+        let (water_id, well_id, spring_id, idiom_id) = (water.id, well.id, spring.id, idiom.id);
+        for lexeme in [water, well, spring, drink, idiom] {
+            lexicon.add_lexeme(lexeme);
+        }
+        // This community had already turned final o into u, so its old form is its own.
+        let change = SoundChange { from: "o".to_string(), to: "u".to_string(), environment: SoundEnvironment::WordFinal };
+        lexicon.apply_sound_change(&inventory(), &change);
+
+        let old_form = place_generator().replace_root(&mut lexicon, water_id, "tura", "water");
+        assert_eq!(old_form.as_deref(), Some("kadu"));
+        assert_eq!(lexicon.graph[&water_id].form_stages(), "kado > kadu (o > u / _#) > tura (replaced)");
+        assert_eq!(lexicon.graph[&well_id].form, "tura-ku");
+        // Each word is rebuilt on its parent's current form, final -ku and all.
+        assert_eq!(lexicon.graph[&spring_id].form_stages(), "kado-ko-ko > kado-ko-ku (o > u / _#) > tura-ku-ku (new stem)");
+        assert_eq!(lexicon.graph[&idiom_id].form, "rad tura");
+    }
+
+    #[test]
+    fn a_replacement_that_sounds_like_another_word_follows_the_homophony_policy() {
+        let mut lexicon = Lexicon::new();
+        let water = root("kado", "noun", "water", &[]);
+        let water_id = water.id;
+        lexicon.add_lexeme(water);
+        lexicon.add_lexeme(root("tura", "noun", "stone", &[]));
+
+        assert_eq!(place_generator().replace_root(&mut lexicon, water_id, "tura", "water"), None);
+        assert_eq!(lexicon.graph[&water_id].form, "kado");
+        assert_eq!(lexicon.collisions.len(), 1);
+        assert_eq!(lexicon.collisions[0].resolution, CollisionResolution::Rejected);
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{Lexeme, Lexicon, Origin, Sense, SenseSource, WordGenerator};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+// Meaning change over time. Every time step each root rolls once per kind of shift, in the
+// order of `DriftKind::ALL`, and changes meaning at most once. Only the core sense drifts: the
+// new meaning becomes the core and the old one is kept as a secondary sense. Apart from taboo
+// replacement a word goes through each kind of shift at most once. Derived words take their
+// meaning from their parent and do not drift on their own.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SemanticDrift {
+    #[serde(default = "default_steps")]
+    pub steps: usize,
+    #[serde(default)]
+    pub rates: DriftRates,
+    #[serde(default)]
+    pub shifts: Vec<KnownShift>, // Hand-picked outcomes, used when their kind strikes that meaning.
+    #[serde(default = "default_taboo_tags")]
+    pub taboo_tags: Vec<String>, // Meanings with any of these tags may be replaced by a euphemism.
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DriftKind {
+    Broadening,   // Loses a specific domain: "hound" -> "dog".
+    Narrowing,    // Keeps only its most salient domain: "meat" (food) -> "meat" (flesh).
+    Amelioration, // Gains a positive connotation.
+    Pejoration,   // Gains a negative connotation.
+    Metonymy,     // Comes to mean something associated with it: "crown" -> "monarchy".
+    Taboo,        // Avoided; another word takes over the meaning as a euphemism.
+}
+
+impl DriftKind {
+    pub const ALL: [DriftKind; 6] = [
+        DriftKind::Broadening,
+        DriftKind::Narrowing,
+        DriftKind::Amelioration,
+        DriftKind::Pejoration,
+        DriftKind::Metonymy,
+        DriftKind::Taboo,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DriftKind::Broadening => "broadening",
+            DriftKind::Narrowing => "narrowing",
+            DriftKind::Amelioration => "amelioration",
+            DriftKind::Pejoration => "pejoration",
+            DriftKind::Metonymy => "metonymy",
+            DriftKind::Taboo => "taboo replacement",
+        }
+    }
+}
+
+// Chance per word per time step of each kind of shift. `taboo` only applies to taboo meanings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DriftRates {
+    pub broadening: f64,
+    pub narrowing: f64,
+    pub amelioration: f64,
+    pub pejoration: f64,
+    pub metonymy: f64,
+    pub taboo: f64,
+}
+
+impl Default for DriftRates {
+    fn default() -> Self {
+        Self {
+            broadening: 0.02,
+            narrowing: 0.02,
+            amelioration: 0.01,
+            pejoration: 0.01,
+            metonymy: 0.01,
+            taboo: 0.1,
+        }
+    }
+}
+
+impl DriftRates {
+    pub fn of(&self, kind: DriftKind) -> f64 {
+        match kind {
+            DriftKind::Broadening => self.broadening,
+            DriftKind::Narrowing => self.narrowing,
+            DriftKind::Amelioration => self.amelioration,
+            DriftKind::Pejoration => self.pejoration,
+            DriftKind::Metonymy => self.metonymy,
+            DriftKind::Taboo => self.taboo,
+        }
+    }
+}
+
+// "When `kind` strikes the word meaning `from`, it comes to mean `to`", e.g. hall -> home.
+// Broadening, narrowing, amelioration and pejoration need one of these to name the new
+// meaning; metonymy borrows a neighbour's meaning, and taboo replacements pick their own
+// euphemism, so neither can be hand-picked.
+#[derive(Debug, Clone, Deserialize)]
+pub struct KnownShift {
+    pub from: String,
+    pub to: String,
+    pub kind: DriftKind,
+}
+
+// One recorded change of a lexeme's core meaning.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeaningShift {
+    pub step: usize,
+    pub kind: DriftKind,
+    pub from: String,
+    pub to: String,
+}
+
+fn default_steps() -> usize { 10 }
+fn default_taboo_tags() -> Vec<String> { vec!["taboo".to_string()] }
+
+// Tags that say what sort of thing a meaning is rather than what it is about; drift leaves them alone.
+const GENERIC_TAGS: &[&str] = &["concrete", "abstract", "mass", "action", "state", "positive", "negative"];
+
+impl Lexeme {
+    // The core meaning's history, e.g. "originally 'hall', later 'home' (metonymy)".
+    pub fn meaning_history(&self) -> Option<String> {
+        let first = self.meaning_shifts.first()?;
+        let mut text = format!("originally '{}'", first.from);
+        for (i, shift) in self.meaning_shifts.iter().enumerate() {
+            let joiner = if i + 1 == self.meaning_shifts.len() { "later" } else { "then" };
+            text.push_str(&format!(", {} '{}' ({})", joiner, shift.to, shift.kind.label()));
+        }
+        Some(text)
+    }
+
+    // Makes `gloss` the core sense; the old core stays on as the first secondary sense.
+    fn shift_meaning(&mut self, step: usize, kind: DriftKind, gloss: String, tags: Vec<String>) {
+        self.meaning_shifts.push(MeaningShift { step, kind, from: self.meaning().to_string(), to: gloss.clone() });
+        self.senses.retain(|s| s.gloss != gloss);
+        self.senses.insert(0, Sense::new(gloss, tags, SenseSource::Drift));
+    }
+}
+
+impl WordGenerator {
+    // Runs `steps` time steps of semantic drift over the lexicon. Idioms keep their meaning.
+    // Returns every shift made, in the order they happened, with the id of the word it struck.
+    pub fn apply_drift(&self, lexicon: &mut Lexicon) -> Vec<(Uuid, MeaningShift)> {
+        let mut shifts = Vec::new();
+        let Some(drift) = &self.lexicon_generation.drift else {
+            return shifts;
+        };
+        let salience = self.lexicon_generation.culture.as_ref()
+            .map(|c| c.domain_salience())
+            .unwrap_or_default();
+        let mut rng = rand::rng();
+
+        let mut ids: Vec<Uuid> = lexicon.graph.values()
+            .filter(|l| l.origin == Origin::Root)
+            .map(|l| l.id)
+            .collect();
+        ids.sort();
+
+        for step in 1..=drift.steps {
+            let mut changed: HashSet<Uuid> = HashSet::new();
+
+            for id in &ids {
+                if changed.contains(id) {
+                    continue;
+                }
+                let lexeme = &lexicon.graph[id];
+                let meaning = lexeme.meaning().to_string();
+                let tags = lexeme.tags().to_vec();
+                let specific: Vec<&String> = tags.iter().filter(|t| !GENERIC_TAGS.contains(&t.as_str())).collect();
+                let is_taboo = tags.iter().any(|t| drift.taboo_tags.contains(t));
+                let undergone: Vec<DriftKind> = lexeme.meaning_shifts.iter().map(|s| s.kind).collect();
+
+                for kind in DriftKind::ALL {
+                    if kind == DriftKind::Taboo && !is_taboo {
+                        continue;
+                    }
+                    if kind != DriftKind::Taboo && undergone.contains(&kind) {
+                        continue;
+                    }
+                    let known = drift.shifts.iter()
+                        .find(|s| s.kind == kind && s.from == meaning)
+                        .map(|s| s.to.clone());
+                    let needs_known = !matches!(kind, DriftKind::Metonymy | DriftKind::Taboo);
+                    if needs_known && known.is_none() {
+                        continue;
+                    }
+                    if !rng.random_bool(drift.rates.of(kind).clamp(0.0, 1.0)) {
+                        continue;
+                    }
+
+                    match kind {
+                        DriftKind::Broadening => {
+                            // Drop the least salient specific domain.
+                            let Some(dropped) = specific.iter()
+                                .min_by(|a, b| salience.of_domain(a).total_cmp(&salience.of_domain(b)))
+                            else {
+                                continue;
+                            };
+                            let new_tags = tags.iter().filter(|t| t != dropped).cloned().collect();
+                            lexicon.graph.get_mut(id).unwrap().shift_meaning(step, kind, known.unwrap(), new_tags);
+                        }
+                        DriftKind::Narrowing => {
+                            // Keep only the most salient specific domain.
+                            if specific.len() < 2 {
+                                continue;
+                            }
+                            let kept = specific.iter()
+                                .max_by(|a, b| salience.of_domain(a).total_cmp(&salience.of_domain(b)))
+                                .unwrap();
+                            let new_tags = tags.iter()
+                                .filter(|t| GENERIC_TAGS.contains(&t.as_str()) || t == kept)
+                                .cloned()
+                                .collect();
+                            lexicon.graph.get_mut(id).unwrap().shift_meaning(step, kind, known.unwrap(), new_tags);
+                        }
+                        DriftKind::Amelioration | DriftKind::Pejoration => {
+                            let (gained, lost) = if kind == DriftKind::Amelioration {
+                                ("positive", "negative")
+                            } else {
+                                ("negative", "positive")
+                            };
+                            // A word only ever takes on one connotation.
+                            if tags.iter().any(|t| t == gained || t == lost) {
+                                continue;
+                            }
+                            let mut new_tags: Vec<String> = tags.iter().filter(|t| *t != lost).cloned().collect();
+                            new_tags.push(gained.to_string());
+                            lexicon.graph.get_mut(id).unwrap().shift_meaning(step, kind, known.unwrap(), new_tags);
+                        }
+                        DriftKind::Metonymy => {
+                            // Take on the original meaning of a root that shares a domain with it.
+                            let associated: Vec<&Lexeme> = ids.iter()
+                                .filter(|other| *other != id)
+                                .filter_map(|other| lexicon.graph.get(other))
+                                .filter(|other| other.origin == Origin::Root && other.meaning_shifts.is_empty())
+                                .filter(|other| other.part_of_speech == lexeme.part_of_speech)
+                                .filter(|other| other.meaning() != meaning)
+                                .filter(|other| other.tags().iter().any(|t| specific.contains(&t)))
+                                .collect();
+                            let Some(associate) = associated.choose(&mut rng) else {
+                                continue;
+                            };
+                            let gloss = known.unwrap_or_else(|| associate.meaning().to_string());
+                            let new_tags = associate.tags().to_vec();
+                            lexicon.graph.get_mut(id).unwrap().shift_meaning(step, kind, gloss, new_tags);
+                        }
+                        DriftKind::Taboo => {
+                            // A root of the same part of speech takes over the meaning as a
+                            // euphemism; the avoided word keeps its meaning but falls out of use.
+                            let candidates: Vec<Uuid> = ids.iter()
+                                .filter(|other| *other != id && !changed.contains(*other))
+                                .filter(|other| {
+                                    let other = &lexicon.graph[*other];
+                                    other.part_of_speech == lexeme.part_of_speech
+                                        && !other.has_sense(&meaning)
+                                        && !other.tags().iter().any(|t| t == "archaic" || drift.taboo_tags.contains(t))
+                                })
+                                .copied()
+                                .collect();
+                            let Some(euphemism) = candidates.choose(&mut rng).copied() else {
+                                continue;
+                            };
+                            lexicon.graph.get_mut(&euphemism).unwrap().shift_meaning(step, kind, meaning.clone(), tags.clone());
+                            changed.insert(euphemism);
+
+                            let avoided = &mut lexicon.graph.get_mut(id).unwrap().senses[0];
+                            avoided.tags.retain(|t| !drift.taboo_tags.contains(t));
+                            avoided.tags.push("archaic".to_string());
+                        }
+                    }
+                    changed.insert(*id);
+                    break;
+                }
+            }
+
+            // A taboo replacement shifts two words in the same step.
+            let mut changed: Vec<Uuid> = changed.into_iter().collect();
+            changed.sort();
+            for id in &changed {
+                let lexeme = &lexicon.graph[id];
+                shifts.extend(lexeme.meaning_shifts.iter().filter(|s| s.step == step).map(|s| (*id, s.clone())));
+            }
+        }
+        shifts
+    }
+}
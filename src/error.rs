@@ -4,6 +4,10 @@ use std::error::Error;
 pub enum ConfigError {
     FileRead(std::io::Error),
     JsonParse(serde_json::Error),
+    UnknownWordList(String),
+    UnknownCommunity(String),
+    MapImport(String),
+    ScriptGlyphs(String),
 }
 
 impl std::fmt::Display for ConfigError {
@@ -11,6 +15,15 @@ impl std::fmt::Display for ConfigError {
         match self {
             ConfigError::FileRead(e) => write!(f, "Failed to read the configuration file: {}", e),
             ConfigError::JsonParse(e) => write!(f, "Failed to parse JSON in the configuration file: {}", e),
+            ConfigError::UnknownWordList(name) => write!(
+                f,
+                "Unknown built-in word list '{}' (available: {})",
+                name,
+                crate::wordlists::BUILTIN_WORD_LISTS.join(", ")
+            ),
+            ConfigError::UnknownCommunity(name) => write!(f, "A dialect link refers to unknown community '{}'", name),
+            ConfigError::MapImport(reason) => write!(f, "Failed to import the dialect map: {}", reason),
+            ConfigError::ScriptGlyphs(reason) => write!(f, "The script's stroke settings are too narrow: {}", reason),
         }
     }
 }
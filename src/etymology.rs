@@ -0,0 +1,106 @@
+use crate::{Lexeme, Lexicon, Origin, WordGenerator};
+use uuid::Uuid;
+
+impl Lexicon {
+    // Every lexeme this one was derived from, nearest parent first and the root last.
+    pub fn ancestors(&self, id: &Uuid) -> Vec<&Lexeme> {
+        let mut ancestors = Vec::new();
+        let mut current = self.graph.get(id);
+        while let Some(parent) = current.and_then(|l| l.parent_id).and_then(|pid| self.graph.get(&pid)) {
+            // Guard against a malformed graph that loops back on itself.
+            if ancestors.iter().any(|a: &&Lexeme| a.id == parent.id) {
+                break;
+            }
+            ancestors.push(parent);
+            current = Some(parent);
+        }
+        ancestors
+    }
+
+    // Every lexeme derived (directly or indirectly) from this one, generation by generation.
+    pub fn descendants(&self, id: &Uuid) -> Vec<&Lexeme> {
+        let mut descendants = Vec::new();
+        let mut frontier = vec![*id];
+        while !frontier.is_empty() {
+            let mut children: Vec<&Lexeme> = self.graph.values()
+                .filter(|l| l.parent_id.is_some_and(|pid| frontier.contains(&pid)))
+                .filter(|l| !descendants.iter().any(|d: &&Lexeme| d.id == l.id))
+                .collect();
+            children.sort_by(|a, b| a.form.cmp(&b.form));
+            frontier = children.iter().map(|l| l.id).collect();
+            descendants.extend(children);
+        }
+        descendants
+    }
+
+    // The generation-0 word this lexeme ultimately comes from (itself, if it is a root).
+    pub fn root_of(&self, id: &Uuid) -> Option<&Lexeme> {
+        let lexeme = self.graph.get(id)?;
+        Some(self.ancestors(id).last().copied().unwrap_or(lexeme))
+    }
+
+    // The full path from the root down to this lexeme, root first.
+    pub fn derivation_chain(&self, id: &Uuid) -> Vec<&Lexeme> {
+        let Some(lexeme) = self.graph.get(id) else {
+            return Vec::new();
+        };
+        let mut chain = self.ancestors(id);
+        chain.reverse();
+        chain.push(lexeme);
+        chain
+    }
+}
+
+impl Lexeme {
+    // The word's form at each historical stage, oldest first, with the change that led to
+    // each later one, e.g. "khazo > kazo (kh > k / #_) > kazu (o > u / _#)".
+    pub fn form_stages(&self) -> String {
+        let mut stages = match self.form_changes.first() {
+            Some((_, oldest)) => oldest.clone(),
+            None => return self.form.clone(),
+        };
+        let later_forms = self.form_changes.iter().skip(1).map(|(_, form)| form).chain([&self.form]);
+        for ((change, _), form) in self.form_changes.iter().zip(later_forms) {
+            stages.push_str(&format!(" > {} ({})", form, change.describe()));
+        }
+        stages
+    }
+}
+
+impl WordGenerator {
+    // A readable history such as "azkazul ← az- (Augmentative) ← kazul ← -ul (LocationOf) ← kaz".
+    // Words whose form changed show every stage of it (see `Lexeme::form_stages`).
+    // Needs the generator so each step can show the affix its rule attached.
+    pub fn format_etymology(&self, lexicon: &Lexicon, id: &Uuid) -> Option<String> {
+        let chain = lexicon.derivation_chain(id);
+        if chain.is_empty() {
+            return None;
+        }
+
+        let mut parts = Vec::new();
+        for lexeme in chain.iter().rev() {
+            parts.push(lexeme.form_stages());
+            if let Some(loan) = &lexeme.borrowed_from {
+                parts.push(format!("{} '{}' (borrowed from {})", loan.donor_form, loan.donor_meaning, loan.language));
+                continue;
+            }
+            if lexeme.origin == Origin::Idiom {
+                let rule_name = lexeme.rule_applied.as_deref().unwrap_or("?");
+                parts.push(format!("idiom ({})", rule_name));
+                continue;
+            }
+            if let Some(rule_name) = &lexeme.rule_applied {
+                let affix = lexeme.affix_applied.clone().or_else(|| {
+                    self.morphology.derivational_rules.iter()
+                        .find(|r| &r.name == rule_name)
+                        .map(|r| r.process.affix().to_string())
+                });
+                match affix {
+                    Some(affix) => parts.push(format!("{} ({})", affix, rule_name)),
+                    None => parts.push(format!("({})", rule_name)),
+                }
+            }
+        }
+        Some(parts.join(" ← "))
+    }
+}
@@ -0,0 +1,640 @@
+use crate::script::{arc_control, xml_escape, Glyph, MarkPosition, Stroke, DOT_RADIUS, GLYPH_SIZE, LOOP_RADIUS, MARK_SCALE, STROKE_WIDTH};
+use crate::transliteration::Written;
+use crate::{PhoneticInventory, ScriptKind, WritingSystem};
+use std::collections::HashSet;
+
+// Which characters the font's glyphs are typed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontMapping {
+    PrivateUse, // Every sign at U+E000 plus its index.
+    Latin,      // One-letter signs on that letter (either case); the rest in the Private Use Area.
+}
+
+// Glyphs are drawn in a GLYPH_SIZE square; the font scales that up by UNITS and sits the
+// bottom of the anchor grid just above the baseline, leaving room for marks above and below.
+const UNITS_PER_EM: u16 = 1000;
+const UNITS: f64 = 8.0;
+const BASELINE: f64 = 85.0;
+const SPACE_ADVANCE: u16 = 400;
+const ASCENDER: i16 = 920;
+const DESCENDER: i16 = -360;
+const PRIVATE_USE_START: u32 = 0xE000;
+const CURVE_STEPS: usize = 12; // Segments per arc.
+const ROUND_STEPS: usize = 16; // Segments per circle.
+
+type Contour = Vec<(i16, i16)>;
+
+// One glyph as the font stores it: straight-sided contours, all wound clockwise so that
+// overlapping strokes merge under the nonzero fill rule.
+struct Outline {
+    advance: u16,
+    contours: Vec<Contour>,
+}
+
+impl Outline {
+    fn bounds(&self) -> Option<(i16, i16, i16, i16)> {
+        let points: Vec<&(i16, i16)> = self.contours.iter().flatten().collect();
+        Some((
+            points.iter().map(|p| p.0).min()?,
+            points.iter().map(|p| p.1).min()?,
+            points.iter().map(|p| p.0).max()?,
+            points.iter().map(|p| p.1).max()?,
+        ))
+    }
+}
+
+impl WritingSystem {
+    // The character each sign is typed as, in sign order. Marks always go in the Private Use
+    // Area, since typing a letter should never produce a bare diacritic.
+    pub fn codepoints(&self, mapping: FontMapping) -> Vec<char> {
+        let mut taken = HashSet::new();
+        self.signs.iter().enumerate()
+            .map(|(i, sign)| {
+                let mut chars = sign.value.chars();
+                let latin = match (mapping, chars.next(), chars.next()) {
+                    (FontMapping::Latin, Some(c), None) if c.is_ascii_alphabetic() && sign.kind.mark_position().is_none() => {
+                        Some(c.to_ascii_lowercase())
+                    }
+                    _ => None,
+                };
+                latin.filter(|c| taken.insert(*c))
+                    .or_else(|| char::from_u32(PRIVATE_USE_START + i as u32))
+                    .unwrap_or(char::REPLACEMENT_CHARACTER)
+            })
+            .collect()
+    }
+
+    // Text as it should be typed to come out in the script with this font's mapping.
+    pub fn encode(&self, inventory: &PhoneticInventory, text: &str, mapping: FontMapping) -> String {
+        let codepoints = self.codepoints(mapping);
+        self.transliterate(inventory, text).iter()
+            .map(|word| word.iter()
+                .map(|unit| match unit {
+                    Written::Sign { base, mark } => std::iter::once(codepoints[*base]).chain(mark.map(|m| codepoints[m])).collect(),
+                    Written::Unknown(text) => text.clone(),
+                })
+                .collect::<String>())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    // A CSV table of the character each sign is typed with.
+    pub fn font_mapping_table(&self, mapping: FontMapping) -> String {
+        let inherent = self.inherent_vowel.as_deref().filter(|_| self.kind == ScriptKind::Abugida);
+        let mut table = String::from("index,value,reading,kind,codepoint\n");
+        for (i, (sign, c)) in self.signs.iter().zip(self.codepoints(mapping)).enumerate() {
+            table.push_str(&format!("{},{},{},{},U+{:04X}\n", i, sign.value, sign.reading(inherent), sign.kind.label(), u32::from(c)));
+        }
+        table
+    }
+
+    // The script as a TrueType font. Marks have no width of their own and sit over (or
+    // after) the sign typed before them, as they do in the rendered SVGs.
+    pub fn to_ttf(&self, family: &str, mapping: FontMapping) -> Vec<u8> {
+        let mut outlines = vec![notdef(), Outline { advance: SPACE_ADVANCE, contours: Vec::new() }];
+        let full = (GLYPH_SIZE * UNITS) as u16;
+        for sign in &self.signs {
+            outlines.push(match sign.kind.mark_position() {
+                Some(position) => {
+                    let (ox, oy) = position.offset();
+                    let advance = if position == MarkPosition::After {
+                        ((ox + GLYPH_SIZE * MARK_SCALE - GLYPH_SIZE) * UNITS).max(0.0) as u16
+                    } else {
+                        0
+                    };
+                    Outline { advance, contours: outline(&sign.glyph, ox - GLYPH_SIZE, oy, MARK_SCALE) }
+                }
+                None => Outline { advance: full, contours: outline(&sign.glyph, 0.0, 0.0, 1.0) },
+            });
+        }
+
+        let mut cmap: Vec<(u16, u16)> = vec![(0x20, 1)];
+        for (i, c) in self.codepoints(mapping).into_iter().enumerate() {
+            let glyph = (i + 2) as u16;
+            cmap.push((u32::from(c) as u16, glyph));
+            if c.is_ascii_lowercase() {
+                cmap.push((u32::from(c.to_ascii_uppercase()) as u16, glyph));
+            }
+        }
+        cmap.sort();
+        cmap.dedup_by_key(|(code, _)| *code);
+
+        build_font(family, &outlines, &cmap)
+    }
+
+    // An HTML page showing every sign in the font, a waterfall of sizes and some sample text.
+    // It loads the font from `font_file`, a path relative to the page.
+    pub fn specimen_html(&self, inventory: &PhoneticInventory, family: &str, font_file: &str, mapping: FontMapping, samples: &[String]) -> String {
+        let inherent = self.inherent_vowel.as_deref().filter(|_| self.kind == ScriptKind::Abugida);
+        let codepoints = self.codepoints(mapping);
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{family} specimen</title>\n<style>\n\
+             @font-face {{ font-family: \"{family}\"; src: url(\"{file}\") format(\"truetype\"); }}\n\
+             body {{ font-family: sans-serif; margin: 2em; }}\n\
+             .script {{ font-family: \"{family}\"; }}\n\
+             .signs {{ display: flex; flex-wrap: wrap; gap: 8px; }}\n\
+             .sign {{ width: 96px; border: 1px solid #ddd; text-align: center; padding: 6px; }}\n\
+             .sign .script {{ font-size: 48px; line-height: 1.6; }}\n\
+             .code {{ color: #888; font-size: 12px; }}\n\
+             </style>\n</head>\n<body>\n<h1>{family}</h1>\n<p>A {kind} of {count} signs (seed {seed}), typed with {mapping}.</p>\n<h2>Signs</h2>\n<div class=\"signs\">\n",
+            family = xml_escape(family),
+            file = xml_escape(font_file),
+            kind = self.kind.label(),
+            count = self.signs.len(),
+            seed = self.seed,
+            mapping = match mapping {
+                FontMapping::PrivateUse => "Private Use Area characters",
+                FontMapping::Latin => "Latin letters where a sign is a single letter",
+            },
+        );
+        for (sign, c) in self.signs.iter().zip(&codepoints) {
+            // Marks are shown on a dotted circle, the usual stand-in for the letter they attach to.
+            let shown = if sign.kind.mark_position().is_some() { format!("\u{25CC}{}", c) } else { c.to_string() };
+            html.push_str(&format!(
+                "<div class=\"sign\"><div class=\"script\">{}</div><div>{}</div><div class=\"code\">U+{:04X}</div></div>\n",
+                xml_escape(&shown), xml_escape(&sign.reading(inherent)), u32::from(*c)
+            ));
+        }
+        html.push_str("</div>\n");
+
+        if let Some(sample) = samples.first() {
+            html.push_str("<h2>Sizes</h2>\n");
+            let encoded = xml_escape(&self.encode(inventory, &sample.to_lowercase(), mapping));
+            for size in [12, 18, 24, 36, 48, 72] {
+                html.push_str(&format!("<p class=\"script\" style=\"font-size: {}px\">{}</p>\n", size, encoded));
+            }
+        }
+        if !samples.is_empty() {
+            html.push_str("<h2>Sample text</h2>\n");
+        }
+        for sample in samples {
+            html.push_str(&format!(
+                "<p class=\"script\" style=\"font-size: 32px\">{}</p>\n<p>{}</p>\n",
+                xml_escape(&self.encode(inventory, &sample.to_lowercase(), mapping)), xml_escape(sample)
+            ));
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+// The outline of `glyph` drawn at `scale` with its top left corner at (x, y), in the same
+// units as the SVGs, converted to font units.
+fn outline(glyph: &Glyph, x: f64, y: f64, scale: f64) -> Vec<Contour> {
+    let to_font = |(px, py): (f64, f64)| (((x + px * scale) * UNITS), ((BASELINE - (y + py * scale)) * UNITS));
+    let half_width = STROKE_WIDTH * scale / 2.0 * UNITS;
+    let mut contours = Vec::new();
+    for stroke in &glyph.strokes {
+        let path: Vec<(f64, f64)> = match *stroke {
+            Stroke::Line { from, to } => vec![glyph.point(from), glyph.point(to)],
+            Stroke::Arc { from, to, bulge } => {
+                let (p0, p2) = (glyph.point(from), glyph.point(to));
+                let p1 = arc_control(p0, p2, bulge);
+                (0..=CURVE_STEPS)
+                    .map(|i| {
+                        let t = i as f64 / CURVE_STEPS as f64;
+                        let u = 1.0 - t;
+                        (u * u * p0.0 + 2.0 * u * t * p1.0 + t * t * p2.0, u * u * p0.1 + 2.0 * u * t * p1.1 + t * t * p2.1)
+                    })
+                    .collect()
+            }
+            Stroke::Loop { at } => {
+                let (cx, cy) = glyph.point(at);
+                (0..=ROUND_STEPS).map(|i| around((cx, cy), LOOP_RADIUS, i)).collect()
+            }
+            Stroke::Dot { at } => {
+                let centre = to_font(glyph.point(at));
+                contours.push(disc(centre, DOT_RADIUS * scale * UNITS));
+                continue;
+            }
+        };
+        // A stroked path is a band along each segment plus a round joint at every point.
+        let path: Vec<(f64, f64)> = path.into_iter().map(to_font).collect();
+        for pair in path.windows(2) {
+            contours.push(band(pair[0], pair[1], half_width));
+        }
+        for point in &path {
+            contours.push(disc(*point, half_width));
+        }
+    }
+    contours.into_iter().filter_map(clockwise).collect()
+}
+
+fn around((cx, cy): (f64, f64), radius: f64, step: usize) -> (f64, f64) {
+    let angle = std::f64::consts::TAU * step as f64 / ROUND_STEPS as f64;
+    (cx + radius * angle.cos(), cy + radius * angle.sin())
+}
+
+fn disc(centre: (f64, f64), radius: f64) -> Vec<(f64, f64)> {
+    (0..ROUND_STEPS).map(|i| around(centre, radius, i)).collect()
+}
+
+fn band((x1, y1): (f64, f64), (x2, y2): (f64, f64), half_width: f64) -> Vec<(f64, f64)> {
+    let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+    if length == 0.0 {
+        return Vec::new();
+    }
+    let (nx, ny) = (-(y2 - y1) / length * half_width, (x2 - x1) / length * half_width);
+    vec![(x1 + nx, y1 + ny), (x2 + nx, y2 + ny), (x2 - nx, y2 - ny), (x1 - nx, y1 - ny)]
+}
+
+// Rounds a contour to font units and winds it clockwise; `None` if nothing is left of it.
+fn clockwise(points: Vec<(f64, f64)>) -> Option<Contour> {
+    let mut contour: Contour = points.iter().map(|(x, y)| (x.round() as i16, y.round() as i16)).collect();
+    contour.dedup();
+    while contour.len() > 1 && contour.first() == contour.last() {
+        contour.pop();
+    }
+    let twice_area: i64 = (0..contour.len())
+        .map(|i| {
+            let (a, b) = (contour[i], contour[(i + 1) % contour.len()]);
+            i64::from(a.0) * i64::from(b.1) - i64::from(b.0) * i64::from(a.1)
+        })
+        .sum();
+    if contour.len() < 3 || twice_area == 0 {
+        return None;
+    }
+    if twice_area > 0 {
+        contour.reverse();
+    }
+    Some(contour)
+}
+
+// The glyph shown for characters the font lacks: an empty box.
+fn notdef() -> Outline {
+    let (left, right, bottom, top) = (100, 500, 0, 700);
+    let outer = vec![(left, bottom), (left, top), (right, top), (right, bottom)];
+    let inner = vec![(left + 50, bottom + 50), (right - 50, bottom + 50), (right - 50, top - 50), (left + 50, top - 50)];
+    Outline { advance: 600, contours: vec![outer, inner] }
+}
+
+// Big-endian writers for the font tables.
+trait Put {
+    fn u16(&mut self, value: u16);
+    fn i16(&mut self, value: i16);
+    fn u32(&mut self, value: u32);
+}
+
+impl Put for Vec<u8> {
+    fn u16(&mut self, value: u16) {
+        self.extend_from_slice(&value.to_be_bytes());
+    }
+    fn i16(&mut self, value: i16) {
+        self.extend_from_slice(&value.to_be_bytes());
+    }
+    fn u32(&mut self, value: u32) {
+        self.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4)
+        .map(|chunk| {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_be_bytes(word)
+        })
+        .fold(0u32, u32::wrapping_add)
+}
+
+// Assembles a TrueType font from outlines (glyph 0 is .notdef) and a sorted character map.
+fn build_font(family: &str, outlines: &[Outline], cmap: &[(u16, u16)]) -> Vec<u8> {
+    let bounds: Vec<Option<(i16, i16, i16, i16)>> = outlines.iter().map(Outline::bounds).collect();
+    let drawn = || bounds.iter().flatten();
+    let x_min = drawn().map(|b| b.0).min().unwrap_or(0);
+    let y_min = drawn().map(|b| b.1).min().unwrap_or(0);
+    let x_max = drawn().map(|b| b.2).max().unwrap_or(0);
+    let y_max = drawn().map(|b| b.3).max().unwrap_or(0);
+    let num_glyphs = outlines.len() as u16;
+
+    // glyf and loca
+    let mut glyf = Vec::new();
+    let mut loca = Vec::new();
+    for (outline, bounds) in outlines.iter().zip(&bounds) {
+        loca.u32(glyf.len() as u32);
+        let Some((gx_min, gy_min, gx_max, gy_max)) = *bounds else { continue };
+        glyf.i16(outline.contours.len() as i16);
+        for value in [gx_min, gy_min, gx_max, gy_max] {
+            glyf.i16(value);
+        }
+        let mut end = 0;
+        for contour in &outline.contours {
+            end += contour.len();
+            glyf.u16((end - 1) as u16);
+        }
+        glyf.u16(0); // No hinting instructions.
+        let points: Vec<&(i16, i16)> = outline.contours.iter().flatten().collect();
+        glyf.extend(std::iter::repeat_n(0x01u8, points.len())); // Every point is on the curve.
+        let mut previous = 0i16;
+        for point in &points {
+            glyf.i16(point.0 - previous);
+            previous = point.0;
+        }
+        previous = 0;
+        for point in &points {
+            glyf.i16(point.1 - previous);
+            previous = point.1;
+        }
+        while glyf.len() % 4 != 0 {
+            glyf.push(0);
+        }
+    }
+    loca.u32(glyf.len() as u32);
+
+    let mut head = Vec::new();
+    head.u32(0x0001_0000); // Version 1.0
+    head.u32(0x0001_0000); // Font revision 1.0
+    head.u32(0);           // Checksum adjustment, filled in once the font is assembled.
+    head.u32(0x5F0F_3CF5); // Magic number
+    head.u16(0x000B);      // Baseline at y = 0, left sidebearing at x = 0, integer scaling.
+    head.u16(UNITS_PER_EM);
+    head.extend_from_slice(&[0; 16]); // Created and modified dates, left at the epoch so output is reproducible.
+    for value in [x_min, y_min, x_max, y_max] {
+        head.i16(value);
+    }
+    head.u16(0);  // Regular style
+    head.u16(8);  // Smallest readable size in pixels
+    head.i16(2);  // Left-to-right glyphs with neutrals
+    head.i16(1);  // Long loca offsets
+    head.i16(0);
+
+    let advance_max = outlines.iter().map(|o| o.advance).max().unwrap_or(0);
+    let mut hhea = Vec::new();
+    hhea.u32(0x0001_0000);
+    hhea.i16(ASCENDER);
+    hhea.i16(DESCENDER);
+    hhea.i16(0); // Line gap
+    hhea.u16(advance_max);
+    hhea.i16(drawn().map(|b| b.0).min().unwrap_or(0));
+    hhea.i16(outlines.iter().zip(&bounds).filter_map(|(o, b)| b.map(|b| o.advance as i16 - b.2)).min().unwrap_or(0));
+    hhea.i16(x_max);
+    hhea.i16(1); // Upright caret
+    hhea.i16(0);
+    hhea.i16(0);
+    hhea.extend_from_slice(&[0; 8]);
+    hhea.i16(0);
+    hhea.u16(num_glyphs);
+
+    let mut hmtx = Vec::new();
+    for (outline, bounds) in outlines.iter().zip(&bounds) {
+        hmtx.u16(outline.advance);
+        hmtx.i16(bounds.map_or(0, |b| b.0));
+    }
+
+    let mut maxp = Vec::new();
+    maxp.u32(0x0001_0000);
+    maxp.u16(num_glyphs);
+    maxp.u16(outlines.iter().map(|o| o.contours.iter().map(Vec::len).sum::<usize>()).max().unwrap_or(0) as u16);
+    maxp.u16(outlines.iter().map(|o| o.contours.len()).max().unwrap_or(0) as u16);
+    maxp.u16(0);
+    maxp.u16(0);
+    maxp.u16(2); // Zones
+    maxp.extend_from_slice(&[0; 16]); // No twilight points, storage, functions, instructions or components.
+
+    let mut os2 = Vec::new();
+    let advances: Vec<u32> = outlines.iter().map(|o| u32::from(o.advance)).filter(|a| *a > 0).collect();
+    os2.u16(4);
+    os2.i16((advances.iter().sum::<u32>() / advances.len().max(1) as u32) as i16);
+    os2.u16(400); // Regular weight
+    os2.u16(5);   // Normal width
+    os2.u16(0);   // Installable embedding
+    for value in [650, 600, 0, 75, 650, 600, 0, 350, 50, 300] {
+        os2.i16(value); // Subscript, superscript and strikeout metrics.
+    }
+    os2.i16(0); // Family class
+    os2.extend_from_slice(&[0; 10]); // PANOSE
+    os2.u32(1);       // Unicode ranges: Basic Latin
+    os2.u32(1 << 28); // ...and the Private Use Area
+    os2.u32(0);
+    os2.u32(0);
+    os2.extend_from_slice(b"NONE");
+    os2.u16(0x00C0); // Regular, with typographic metrics
+    os2.u16(cmap.first().map_or(0, |c| c.0));
+    os2.u16(cmap.last().map_or(0, |c| c.0));
+    os2.i16(ASCENDER);
+    os2.i16(DESCENDER);
+    os2.i16(0);
+    os2.u16(y_max.max(ASCENDER) as u16);
+    os2.u16((-y_min).max(-DESCENDER) as u16);
+    os2.u32(1); // Latin 1 code page
+    os2.u32(0);
+    os2.i16(500); // x-height
+    os2.i16(700); // Cap height
+    os2.u16(0);
+    os2.u16(0x20);
+    os2.u16(1);
+
+    // cmap: format 4, one segment per character plus the closing 0xFFFF segment.
+    let seg_count = cmap.len() as u16 + 1;
+    let entry_selector = 15 - seg_count.leading_zeros() as u16;
+    let search_range = 2 * (1 << entry_selector);
+    let mut subtable = Vec::new();
+    subtable.u16(4);
+    subtable.u16(16 + 8 * seg_count);
+    subtable.u16(0);
+    subtable.u16(seg_count * 2);
+    subtable.u16(search_range);
+    subtable.u16(entry_selector);
+    subtable.u16(seg_count * 2 - search_range);
+    for (code, _) in cmap {
+        subtable.u16(*code);
+    }
+    subtable.u16(0xFFFF);
+    subtable.u16(0);
+    for (code, _) in cmap {
+        subtable.u16(*code);
+    }
+    subtable.u16(0xFFFF);
+    for (code, glyph) in cmap {
+        subtable.u16(glyph.wrapping_sub(*code));
+    }
+    subtable.u16(1);
+    subtable.extend(std::iter::repeat_n(0u8, 2 * seg_count as usize));
+    let mut cmap_table = Vec::new();
+    cmap_table.u16(0);
+    cmap_table.u16(2);
+    for (platform, encoding) in [(0, 3), (3, 1)] {
+        cmap_table.u16(platform);
+        cmap_table.u16(encoding);
+        cmap_table.u32(20);
+    }
+    cmap_table.extend(subtable);
+
+    let postscript: String = family.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+    let names = [
+        (1, family.to_string()),
+        (2, "Regular".to_string()),
+        (3, format!("{};Regular", postscript)),
+        (4, family.to_string()),
+        (5, "Version 1.000".to_string()),
+        (6, postscript),
+    ];
+    let mut name = Vec::new();
+    let mut strings = Vec::new();
+    name.u16(0);
+    name.u16(names.len() as u16);
+    name.u16(6 + 12 * names.len() as u16);
+    for (id, text) in &names {
+        let encoded: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        for value in [3, 1, 0x0409, *id, encoded.len() as u16, strings.len() as u16] {
+            name.u16(value);
+        }
+        strings.extend(encoded);
+    }
+    name.extend(strings);
+
+    let mut post = Vec::new();
+    post.u32(0x0003_0000); // No glyph names
+    post.u32(0);
+    post.i16(-100);
+    post.i16(50);
+    post.extend_from_slice(&[0; 20]);
+
+    // Tables in tag order, each padded to four bytes.
+    let tables: [(&[u8; 4], Vec<u8>); 10] = [
+        (b"OS/2", os2),
+        (b"cmap", cmap_table),
+        (b"glyf", glyf),
+        (b"head", head),
+        (b"hhea", hhea),
+        (b"hmtx", hmtx),
+        (b"loca", loca),
+        (b"maxp", maxp),
+        (b"name", name),
+        (b"post", post),
+    ];
+    let count = tables.len() as u16;
+    let entry_selector = 15 - count.leading_zeros() as u16;
+    let search_range = 16 * (1 << entry_selector);
+    let mut font = Vec::new();
+    font.u32(0x0001_0000);
+    font.u16(count);
+    font.u16(search_range);
+    font.u16(entry_selector);
+    font.u16(count * 16 - search_range);
+    let mut offset = 12 + 16 * tables.len();
+    let mut head_offset = 0;
+    for (tag, data) in &tables {
+        if *tag == b"head" {
+            head_offset = offset;
+        }
+        font.extend_from_slice(*tag);
+        font.u32(checksum(data));
+        font.u32(offset as u32);
+        font.u32(data.len() as u32);
+        offset += data.len().div_ceil(4) * 4;
+    }
+    for (_, data) in &tables {
+        font.extend_from_slice(data);
+        while font.len() % 4 != 0 {
+            font.push(0);
+        }
+    }
+    let adjustment = 0xB1B0_AFBAu32.wrapping_sub(checksum(&font));
+    font[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+    font
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::script::SignKind;
+    use crate::test_support;
+
+    fn script(signs: &[(&str, SignKind)]) -> WritingSystem {
+        test_support::script(ScriptKind::Abugida, Some("a"), signs)
+    }
+
+    fn u16_at(data: &[u8], at: usize) -> u16 {
+        u16::from_be_bytes([data[at], data[at + 1]])
+    }
+
+    fn u32_at(data: &[u8], at: usize) -> u32 {
+        u32::from_be_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]])
+    }
+
+    // Each table's tag, recorded checksum and bytes, from the table directory.
+    fn tables(font: &[u8]) -> Vec<(String, u32, &[u8])> {
+        (0..u16_at(font, 4) as usize)
+            .map(|i| {
+                let entry = 12 + 16 * i;
+                let offset = u32_at(font, entry + 8) as usize;
+                let length = u32_at(font, entry + 12) as usize;
+                (String::from_utf8_lossy(&font[entry..entry + 4]).into_owned(), u32_at(font, entry + 4), &font[offset..offset + length])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn checksum_pads_the_last_word_with_zeros() {
+        assert_eq!(checksum(&[]), 0);
+        assert_eq!(checksum(&[0, 0, 0, 1, 0, 0, 0, 2]), 3);
+        assert_eq!(checksum(&[1, 2]), 0x0102_0000);
+        assert_eq!(checksum(&[0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 2]), 1);
+    }
+
+    #[test]
+    fn table_checksums_and_adjustment_are_consistent() {
+        let font = script(&[("k", SignKind::Consonant), ("i", SignKind::VowelMark(MarkPosition::Above))])
+            .to_ttf("Test Script", FontMapping::PrivateUse);
+        assert_eq!(font.len() % 4, 0);
+        let tables = tables(&font);
+        let tags: Vec<&str> = tables.iter().map(|(tag, _, _)| tag.as_str()).collect();
+        assert_eq!(tags, ["OS/2", "cmap", "glyf", "head", "hhea", "hmtx", "loca", "maxp", "name", "post"]);
+
+        for (tag, recorded, data) in &tables {
+            let mut data = data.to_vec();
+            if tag == "head" {
+                data[8..12].fill(0); // The checksum adjustment is left out of head's own checksum.
+            }
+            assert_eq!(checksum(&data), *recorded, "{} checksum", tag);
+        }
+        assert_eq!(checksum(&font), 0xB1B0_AFBA);
+    }
+
+    #[test]
+    fn cmap_is_format_4_with_a_closing_segment() {
+        let font = script(&[("k", SignKind::Consonant), ("t", SignKind::Consonant), ("i", SignKind::VowelMark(MarkPosition::Above))])
+            .to_ttf("Test Script", FontMapping::PrivateUse);
+        let tables = tables(&font);
+        let (_, _, cmap) = tables.iter().find(|(tag, _, _)| tag == "cmap").unwrap();
+        assert_eq!(u16_at(cmap, 2), 2); // Unicode and Windows encoding records...
+        assert_eq!(u32_at(cmap, 8), u32_at(cmap, 16)); // ...sharing one subtable.
+
+        let subtable = &cmap[u32_at(cmap, 8) as usize..];
+        assert_eq!(u16_at(subtable, 0), 4);
+        assert_eq!(u16_at(subtable, 2) as usize, subtable.len());
+        let seg_count = u16_at(subtable, 6) as usize / 2;
+        assert_eq!(seg_count, 5); // Space, three signs and 0xFFFF.
+        let search_range = u16_at(subtable, 8) as usize;
+        assert_eq!(search_range, 8);
+        assert_eq!(u16_at(subtable, 10), 2);
+        assert_eq!(u16_at(subtable, 12) as usize, 2 * seg_count - search_range);
+
+        // End codes, then (after a reserved pad) start codes and deltas.
+        let array = |index: usize, at: usize| u16_at(subtable, 14 + index * 2 * seg_count + 2 * at + if index > 0 { 2 } else { 0 });
+        let glyph_for = |code: u16| (0..seg_count)
+            .find(|&s| array(1, s) <= code && code <= array(0, s))
+            .map(|s| code.wrapping_add(array(2, s)));
+        assert_eq!(array(0, seg_count - 1), 0xFFFF);
+        assert_eq!(array(1, seg_count - 1), 0xFFFF);
+        assert_eq!(glyph_for(0x20), Some(1));
+        assert_eq!(glyph_for(0xE000), Some(2));
+        assert_eq!(glyph_for(0xE001), Some(3));
+        assert_eq!(glyph_for(0xE002), Some(4));
+        assert_eq!(glyph_for(0x41), None);
+    }
+
+    #[test]
+    fn latin_mapping_covers_both_cases() {
+        let writing = script(&[("k", SignKind::Consonant), ("kh", SignKind::Consonant), ("i", SignKind::VowelMark(MarkPosition::Above))]);
+        assert_eq!(writing.codepoints(FontMapping::Latin), ['k', '\u{E001}', '\u{E002}']);
+        let font = writing.to_ttf("Test Script", FontMapping::Latin);
+        let tables = tables(&font);
+        let (_, _, cmap) = tables.iter().find(|(tag, _, _)| tag == "cmap").unwrap();
+        let subtable = &cmap[20..];
+        let seg_count = u16_at(subtable, 6) as usize / 2;
+        let end_codes: Vec<u16> = (0..seg_count).map(|s| u16_at(subtable, 14 + 2 * s)).collect();
+        assert_eq!(end_codes, [0x20, 'K' as u16, 'k' as u16, 0xE001, 0xE002, 0xFFFF]);
+    }
+}
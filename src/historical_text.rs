@@ -0,0 +1,379 @@
+use crate::naming::capitalize;
+use crate::{ConfigError, Lexicon, NameKind, PhoneticInventory, Register, WordGenerator};
+use rand::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// Longer texts in the language (royal decrees, grave inscriptions, chronicles) built from
+// clause templates, each sentence shown with an interlinear gloss and a free translation.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct TextSettings {
+    #[serde(default)]
+    pub templates: Vec<TextTemplate>, // Falls back to `default_text_templates()` when empty.
+    #[serde(default)]
+    pub history: Option<String>,      // A JSON history log to take events from, relative to the config file.
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum TextKind {
+    Decree,
+    Inscription,
+    Chronicle,
+}
+
+impl TextKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            TextKind::Decree => "decree",
+            TextKind::Inscription => "inscription",
+            TextKind::Chronicle => "chronicle",
+        }
+    }
+
+    // Decrees and inscriptions are carved or proclaimed in court speech; chronicles are not.
+    pub fn register(self) -> Register {
+        match self {
+            TextKind::Decree | TextKind::Inscription => Register::Formal,
+            TextKind::Chronicle => Register::Common,
+        }
+    }
+}
+
+// A recipe for one style of text. The opening runs once, `per_event` once for every event
+// in the history (oldest first), and the closing once at the end.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TextTemplate {
+    pub name: String,
+    pub kind: TextKind,
+    #[serde(default)]
+    pub opening: Vec<Clause>,
+    #[serde(default)]
+    pub per_event: Vec<Clause>,
+    #[serde(default)]
+    pub closing: Vec<Clause>,
+}
+
+// One sentence. Each role is a list of space-separated slots:
+//   "noun", "verb", "adj", ...  a random word with that part of speech
+//   "=stone"                    the word meaning "stone"
+//   "@person", "@place", ...    a name of that kind; the same slot is the same name throughout
+//                               a text, so write "@person#2" for a second person
+//   "$actor", "$deed", "$target", "$place", "$year"  from the current history event
+// Subject, verb and object follow the grammar's word order; the oblique (a place, a time)
+// comes last. The translation may use {subject}, {verb}, {object}, {oblique} and $fields.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct Clause {
+    #[serde(default)]
+    pub subject: String,
+    #[serde(default)]
+    pub verb: String,
+    #[serde(default)]
+    pub object: String,
+    #[serde(default)]
+    pub oblique: String,
+    #[serde(default)]
+    pub translation: Option<String>,
+}
+
+pub const EVENT_FIELDS: &[&str] = &["actor", "deed", "target", "place", "year"];
+
+// One entry of a history log. Actors, targets and places are proper names and are written
+// as they are; the deed is an English verb, looked up among the lexicon's meanings.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct HistoryEvent {
+    #[serde(default)]
+    pub year: Option<i64>,
+    #[serde(default)]
+    pub actor: Option<String>,
+    #[serde(default)]
+    pub deed: Option<String>,
+    #[serde(default)]
+    pub target: Option<String>,
+    #[serde(default)]
+    pub place: Option<String>,
+}
+
+// A history log is either a bare array of events or an object with an "events" array.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HistoryLog {
+    Events(Vec<HistoryEvent>),
+    Wrapped { events: Vec<HistoryEvent> },
+}
+
+// Reads a history log, oldest event first.
+pub fn load_history(path: &str) -> Result<Vec<HistoryEvent>, ConfigError> {
+    let contents = std::fs::read_to_string(path).map_err(ConfigError::FileRead)?;
+    let mut events = match serde_json::from_str(&contents).map_err(ConfigError::JsonParse)? {
+        HistoryLog::Events(events) | HistoryLog::Wrapped { events } => events,
+    };
+    events.sort_by_key(|e| e.year.unwrap_or(i64::MIN));
+    Ok(events)
+}
+
+pub fn default_text_templates() -> Vec<TextTemplate> {
+    let clause = |subject: &str, verb: &str, object: &str, oblique: &str, translation: Option<&str>| Clause {
+        subject: subject.to_string(),
+        verb: verb.to_string(),
+        object: object.to_string(),
+        oblique: oblique.to_string(),
+        translation: translation.map(str::to_string),
+    };
+    vec![
+        TextTemplate {
+            name: "Royal decree".to_string(),
+            kind: TextKind::Decree,
+            opening: vec![clause("@person", "verb", "@polity", "", Some("Thus speaks {subject}: {subject} {verb} {object}"))],
+            per_event: vec![],
+            closing: vec![
+                clause("adj noun", "verb", "noun", "", None),
+                clause("@polity", "verb", "adj noun", "", None),
+                clause("@person", "verb", "noun", "", Some("So {subject} has decreed: let {subject} {verb} {object}")),
+            ],
+        },
+        TextTemplate {
+            name: "Grave inscription".to_string(),
+            kind: TextKind::Inscription,
+            opening: vec![clause("@person", "verb", "adj noun", "@place", Some("Here lies {subject}, who would {verb} {object} in {oblique}"))],
+            per_event: vec![],
+            closing: vec![clause("@clan", "verb", "@person", "", Some("{subject} {verb} {object} forever"))],
+        },
+        TextTemplate {
+            name: "Chronicle".to_string(),
+            kind: TextKind::Chronicle,
+            opening: vec![clause("@polity", "verb", "noun", "", None)],
+            per_event: vec![clause("$actor", "$deed", "$target", "$place", Some("In the year $year, {subject} {verb} {object} at {oblique}"))],
+            closing: vec![],
+        },
+    ]
+}
+
+// A written word with its interlinear gloss.
+#[derive(Debug, Clone)]
+pub struct GlossedWord {
+    pub form: String,
+    pub gloss: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct InterlinearSentence {
+    pub words: Vec<GlossedWord>,
+    pub translation: String,
+}
+
+impl InterlinearSentence {
+    pub fn text(&self) -> String {
+        let words: Vec<&str> = self.words.iter().map(|w| w.form.as_str()).collect();
+        capitalize(&words.join(" ")) + "."
+    }
+
+    // Three lines: the sentence, each word's gloss aligned under it, and the translation.
+    pub fn interlinear(&self) -> String {
+        let (mut forms, mut glosses) = (String::new(), String::new());
+        for word in &self.words {
+            let width = word.form.chars().count().max(word.gloss.chars().count()) + 2;
+            forms.push_str(&format!("{:<width$}", word.form, width = width));
+            glosses.push_str(&format!("{:<width$}", word.gloss, width = width));
+        }
+        format!("{}\n{}\n'{}'", forms.trim_end(), glosses.trim_end(), self.translation)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoricalText {
+    pub kind: TextKind,
+    pub template: String,
+    pub sentences: Vec<InterlinearSentence>,
+}
+
+impl HistoricalText {
+    // The text as it would be written, one sentence per line.
+    pub fn text(&self) -> String {
+        self.sentences.iter().map(|s| s.text()).collect::<Vec<_>>().join("\n")
+    }
+
+    // The text with every sentence glossed, for a plain-text export.
+    pub fn render(&self) -> String {
+        let mut out = format!("{} ({}, {} register)\n\n{}\n\n", self.template, self.kind.label(), self.kind.register().label(), self.text());
+        for (i, sentence) in self.sentences.iter().enumerate() {
+            out.push_str(&format!("{}.\n{}\n\n", i + 1, sentence.interlinear()));
+        }
+        out
+    }
+}
+
+impl WordGenerator {
+    pub fn text_templates(&self) -> Vec<TextTemplate> {
+        if self.texts.templates.is_empty() {
+            default_text_templates()
+        } else {
+            self.texts.templates.clone()
+        }
+    }
+
+    // Writes one text from `template`. Chronicles need events: with an empty history, `count`
+    // events are made up, each filled in from the lexicon and the naming engine as it is told.
+    pub fn generate_text(&self, inventory: &PhoneticInventory, lexicon: &Lexicon, template: &TextTemplate, history: &[HistoryEvent], count: usize) -> HistoricalText {
+        let mut rng = rand::rng();
+        let invented: Vec<HistoryEvent>;
+        let events = if history.is_empty() {
+            let mut year = rng.random_range(100..1000);
+            invented = (0..count)
+                .map(|_| {
+                    year += rng.random_range(1..=30);
+                    HistoryEvent { year: Some(year), ..Default::default() }
+                })
+                .collect();
+            &invented[..]
+        } else {
+            history
+        };
+
+        let mut writer = TextWriter { generator: self, inventory, lexicon, register: template.kind.register(), names: HashMap::new() };
+        let blank = HistoryEvent::default();
+        let last = events.len().saturating_sub(1);
+        let mut sentences = Vec::new();
+        for clause in &template.opening {
+            sentences.push(writer.write(clause, 0, events.first().unwrap_or(&blank)));
+        }
+        for (index, event) in events.iter().enumerate() {
+            for clause in &template.per_event {
+                sentences.push(writer.write(clause, index, event));
+            }
+        }
+        for clause in &template.closing {
+            sentences.push(writer.write(clause, last, events.last().unwrap_or(&blank)));
+        }
+        HistoricalText { kind: template.kind, template: template.name.clone(), sentences }
+    }
+}
+
+// Fills in clauses for one text, remembering the names it has already made up.
+struct TextWriter<'a> {
+    generator: &'a WordGenerator,
+    inventory: &'a PhoneticInventory,
+    lexicon: &'a Lexicon,
+    register: Register,
+    names: HashMap<String, GlossedWord>,
+}
+
+impl TextWriter<'_> {
+    // Writes one clause about the event at `index` in the text's list of events.
+    fn write(&mut self, clause: &Clause, index: usize, event: &HistoryEvent) -> InterlinearSentence {
+        let subject = self.role(&clause.subject, "noun", index, event);
+        let verb = self.role(&clause.verb, "verb", index, event);
+        let object = self.role(&clause.object, "noun", index, event);
+        let oblique = self.role(&clause.oblique, "noun", index, event);
+
+        let english = |words: &[(GlossedWord, String)]| words.iter().map(|(_, e)| e.as_str()).collect::<Vec<_>>().join(" ");
+        let pattern = clause.translation.clone().unwrap_or_else(|| "{subject} {verb} {object} {oblique}".to_string());
+        let mut translation = pattern
+            .replace("{subject}", &english(&subject))
+            .replace("{verb}", &english(&verb))
+            .replace("{object}", &english(&object))
+            .replace("{oblique}", &english(&oblique));
+        for field in EVENT_FIELDS {
+            translation = translation.replace(&format!("${}", field), &event_value(event, field).unwrap_or_else(|| "?".to_string()));
+        }
+        let translation = capitalize(&translation.split_whitespace().collect::<Vec<_>>().join(" ")) + ".";
+
+        let words = self.generator.grammar.arrange(subject, verb, object).into_iter()
+            .chain([oblique])
+            .flatten()
+            .map(|(word, _)| word)
+            .collect();
+        InterlinearSentence { words, translation }
+    }
+
+    // The words filling one role, each with its English rendering for the translation.
+    fn role(&mut self, slots: &str, default_pos: &str, index: usize, event: &HistoryEvent) -> Vec<(GlossedWord, String)> {
+        slots.split_whitespace().map(|slot| self.slot(slot, default_pos, index, event)).collect()
+    }
+
+    // Words asked for by meaning fall back to any word of the slot's part of speech when the
+    // lexicon has no word with that sense.
+    fn slot(&mut self, slot: &str, default_pos: &str, index: usize, event: &HistoryEvent) -> (GlossedWord, String) {
+        if let Some(field) = slot.strip_prefix('$') {
+            return match (field, event_value(event, field)) {
+                ("deed", Some(deed)) => self.word(Some("verb"), Some(&deed)).unwrap_or_else(|| self.slot("verb", default_pos, index, event)),
+                ("deed", None) => self.slot("verb", default_pos, index, event),
+                (_, Some(value)) => (GlossedWord { form: value.clone(), gloss: value.clone() }, value),
+                // Nothing recorded: make up someone or somewhere fitting, once per event.
+                ("actor", None) => self.slot(&format!("@person#actor{}", index), default_pos, index, event),
+                ("place", None) => self.slot(&format!("@place#place{}", index), default_pos, index, event),
+                (_, None) => self.slot(default_pos, default_pos, index, event),
+            };
+        }
+        if let Some(kind) = slot.strip_prefix('@') {
+            return self.name(slot, kind.split('#').next().unwrap_or(kind));
+        }
+        if let Some(gloss) = slot.strip_prefix('=') {
+            return self.word(None, Some(gloss)).unwrap_or_else(|| self.slot(default_pos, default_pos, index, event));
+        }
+        self.word(Some(slot), None).unwrap_or_else(|| {
+            // Not even one word of this part of speech; stand in like the "<noun>" of `generate_sentence`.
+            let placeholder = format!("<{}>", slot);
+            (GlossedWord { form: placeholder.clone(), gloss: placeholder }, slot.to_string())
+        })
+    }
+
+    // A random word with part of speech `pos` and sense `gloss` (either may be left open), in
+    // the text's register. It is glossed and translated by the sense that matched, else by
+    // its core meaning.
+    fn word(&self, pos: Option<&str>, gloss: Option<&str>) -> Option<(GlossedWord, String)> {
+        let mut rng = rand::rng();
+        let lexeme = self.lexicon.graph.values()
+            .filter(|l| pos.is_none_or(|p| l.part_of_speech == p))
+            .filter(|l| gloss.is_none_or(|g| l.has_sense(g)))
+            .choose(&mut rng)?;
+        let meaning = gloss.unwrap_or(lexeme.meaning());
+        let variant = self.generator.register_variant(self.inventory, lexeme, self.register);
+        let mut gloss = meaning.replace(' ', ".");
+        // Honorifics are shown as their own morpheme, on the side they attach to.
+        for process in &variant.processes {
+            if let Some(affix) = process.strip_prefix("honorific ") {
+                gloss = if affix.ends_with('-') { format!("HON-{}", gloss) } else { format!("{}-HON", gloss) };
+            }
+        }
+        Some((GlossedWord { form: variant.form, gloss }, meaning.to_string()))
+    }
+
+    // The name for `slot`, made up the first time it is used in this text.
+    fn name(&mut self, slot: &str, kind: &str) -> (GlossedWord, String) {
+        if let Some(word) = self.names.get(slot) {
+            return (word.clone(), word.form.clone());
+        }
+        let kind = match kind {
+            "place" => NameKind::Place,
+            "polity" => NameKind::Polity,
+            "clan" => NameKind::Clan,
+            _ => NameKind::Person,
+        };
+        let mut templates: Vec<_> = self.generator.name_templates(self.inventory).into_iter().filter(|t| t.kind == kind).collect();
+        templates.shuffle(&mut rand::rng());
+        // A template can fail for want of a fitting word, so try them all; failing that, any
+        // noun will do as a bare name.
+        let name = templates.iter()
+            .find_map(|t| self.generator.generate_name(self.lexicon, self.inventory, t))
+            .map(|n| n.name)
+            .or_else(|| {
+                let noun = self.lexicon.graph.values().filter(|l| l.part_of_speech == "noun").choose(&mut rand::rng())?;
+                Some(capitalize(&noun.form.replace('-', "")))
+            })
+            .unwrap_or_else(|| "<name>".to_string());
+        let word = GlossedWord { form: name.clone(), gloss: name.clone() };
+        self.names.insert(slot.to_string(), word.clone());
+        (word, name)
+    }
+}
+
+fn event_value(event: &HistoryEvent, field: &str) -> Option<String> {
+    match field {
+        "year" => event.year.map(|y| y.to_string()),
+        "actor" => event.actor.clone(),
+        "deed" => event.deed.clone(),
+        "target" => event.target.clone(),
+        "place" => event.place.clone(),
+        _ => None,
+    }
+}
@@ -1,14 +1,137 @@
+pub mod borrowing;
+pub mod concepts;
+pub mod culture;
+pub mod dialect;
+pub mod drift;
 pub mod error;
+pub mod etymology;
+pub mod font;
+pub mod historical_text;
+pub mod map_import;
+pub mod metaphor;
+pub mod naming;
+pub mod pronunciation;
+pub mod query;
+pub mod register;
+pub mod romanization;
+pub mod script;
+pub mod sound_change;
+pub mod speech;
+pub mod symbolism;
+#[cfg(test)]
+mod test_support;
+pub mod transliteration;
+pub mod validation;
+pub mod wordlists;
+pub use culture::Culture;
+pub use dialect::{CommunityGraph, DialectSettings, DialectSimulation};
+pub use drift::{DriftKind, MeaningShift, SemanticDrift};
 pub use error::ConfigError;
+pub use font::FontMapping;
+pub use historical_text::{HistoricalText, HistoryEvent, TextKind, TextSettings, TextTemplate};
+pub use metaphor::ConceptualMetaphor;
+pub use naming::{GeneratedName, NameKind, NameTemplate, Naming};
+pub use pronunciation::{PronunciationSettings, StressRule};
+pub use query::{FormMatch, LexiconQuery, SortOrder};
+pub use register::{Register, RegisterSettings, RegisterVariant};
+pub use romanization::Scheme;
+pub use script::{ScriptKind, ScriptSettings, WritingSystem};
+pub use sound_change::{FormChange, SoundChange, SoundEnvironment};
+pub use speech::{Speech, SpeechSettings};
+pub use symbolism::SoundSymbolism;
+pub use transliteration::Written;
 use rand::prelude::*;
-use serde::Deserialize;
-use std::{fs::File, io::Read};
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::Read, path::Path};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Phoneme {
     grapheme: String,
     sound_type: String,
+    #[serde(default)]
+    features: Vec<String>, // e.g. ["high", "front"]; inferred for common vowels when empty.
+    #[serde(default)]
+    ipa: Option<String>,   // Guessed from the grapheme when missing; see romanization.
+    #[serde(default)]
+    romanizations: HashMap<String, String>, // Scheme name -> spelling, e.g. "scholarly" -> "š".
+}
+
+impl Phoneme {
+    pub fn grapheme(&self) -> &str {
+        &self.grapheme
+    }
+
+    pub fn is_vowel(&self) -> bool {
+        self.sound_type == "Vowel"
+    }
+
+    // Checks the configured features, falling back to `default_features` when none were given.
+    pub fn has_feature(&self, feature: &str) -> bool {
+        if !self.features.is_empty() {
+            return self.features.iter().any(|f| f == feature);
+        }
+        default_features(&self.grapheme, self.is_vowel()).contains(&feature)
+    }
+
+    pub fn features(&self) -> Vec<&str> {
+        if !self.features.is_empty() {
+            return self.features.iter().map(|f| f.as_str()).collect();
+        }
+        default_features(&self.grapheme, self.is_vowel()).to_vec()
+    }
+}
+
+// The usual features of a Latin letter (or common digraph): height and backness for vowels,
+// place, manner and voicing for consonants. Unknown graphemes fall back to their first letter,
+// so "gr" is treated like "g" and "aa" like "a".
+pub fn default_features(grapheme: &str, vowel: bool) -> &'static [&'static str] {
+    let lookup = |g: &str| -> Option<&'static [&'static str]> {
+        let features: &'static [&'static str] = match (vowel, g) {
+            (true, "i") => &["high", "front"],
+            (true, "y") => &["high", "front", "rounded"],
+            (true, "e") => &["mid", "front"],
+            (true, "a") => &["low", "central"],
+            (true, "o") => &["mid", "back", "rounded"],
+            (true, "u") => &["high", "back", "rounded"],
+            (false, "p") => &["labial", "stop", "voiceless"],
+            (false, "b") => &["labial", "stop", "voiced"],
+            (false, "m") => &["labial", "nasal", "voiced"],
+            (false, "f") => &["labial", "fricative", "voiceless"],
+            (false, "v") => &["labial", "fricative", "voiced"],
+            (false, "w") => &["labial", "glide", "voiced"],
+            (false, "th") => &["dental", "fricative", "voiceless"],
+            (false, "dh") => &["dental", "fricative", "voiced"],
+            (false, "t") => &["alveolar", "stop", "voiceless"],
+            (false, "d") => &["alveolar", "stop", "voiced"],
+            (false, "n") => &["alveolar", "nasal", "voiced"],
+            (false, "s") => &["alveolar", "fricative", "voiceless"],
+            (false, "z") => &["alveolar", "fricative", "voiced"],
+            (false, "l") => &["alveolar", "liquid", "lateral", "voiced"],
+            (false, "r") => &["alveolar", "liquid", "voiced"],
+            (false, "sh") => &["postalveolar", "fricative", "voiceless"],
+            (false, "zh") => &["postalveolar", "fricative", "voiced"],
+            (false, "ch") => &["postalveolar", "affricate", "voiceless"],
+            (false, "j") => &["postalveolar", "affricate", "voiced"],
+            (false, "y") => &["palatal", "glide", "voiced"],
+            (false, "k") | (false, "c") => &["velar", "stop", "voiceless"],
+            (false, "g") => &["velar", "stop", "voiced"],
+            (false, "ng") => &["velar", "nasal", "voiced"],
+            (false, "kh") | (false, "x") => &["velar", "fricative", "voiceless"],
+            (false, "gh") => &["velar", "fricative", "voiced"],
+            (false, "q") => &["uvular", "stop", "voiceless"],
+            (false, "h") => &["glottal", "fricative", "voiceless"],
+            _ => return None,
+        };
+        Some(features)
+    };
+    let first = grapheme.chars().next().map(|c| &grapheme[..c.len_utf8()]).unwrap_or("");
+    lookup(grapheme).or_else(|| lookup(first)).unwrap_or(&[])
+}
+
+// How many features one sound has that the other lacks, counted both ways.
+pub fn feature_distance(a: &[&str], b: &[&str]) -> usize {
+    a.iter().filter(|f| !b.contains(f)).count() + b.iter().filter(|f| !a.contains(f)).count()
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -19,6 +142,20 @@ pub struct Grammar {
 
 fn default_word_order() -> String { "SVO".to_string() }
 
+impl Grammar {
+    // Puts a clause's subject, verb and object in the configured word order (SVO if unknown).
+    pub fn arrange<T>(&self, subject: T, verb: T, object: T) -> [T; 3] {
+        match self.word_order.as_str() {
+            "SOV" => [subject, object, verb],
+            "VSO" => [verb, subject, object],
+            "VOS" => [verb, object, subject],
+            "OSV" => [object, subject, verb],
+            "OVS" => [object, verb, subject],
+            _ => [subject, verb, object],
+        }
+    }
+}
+
 pub struct PhoneticInventory {
     vowels: Vec<Phoneme>,
     consonants: Vec<Phoneme>,
@@ -40,6 +177,33 @@ impl PhoneticInventory {
         let mut rng = rand::rng();
         self.vowels.choose(&mut rng)
     }
+
+    fn get_weighted_phoneme(phonemes: &[Phoneme], weight: impl Fn(&Phoneme) -> f64) -> Option<&Phoneme> {
+        let mut rng = rand::rng();
+        phonemes.choose_weighted(&mut rng, |p| weight(p)).ok()
+    }
+
+    // Splits a form into phonemes, always taking the longest grapheme that matches
+    // (so "kh" is one consonant, not "k" + "h"). Characters outside the inventory,
+    // such as the hyphens affixes carry, are skipped.
+    pub fn segment(&self, form: &str) -> Vec<&Phoneme> {
+        self.tokenize(form).into_iter().filter_map(|(_, phoneme)| phoneme).collect()
+    }
+
+    // Like `segment`, but keeps every piece of the form in order: each phoneme with its
+    // text, and each character outside the inventory paired with `None`.
+    pub fn tokenize<'a>(&self, form: &'a str) -> Vec<(&'a str, Option<&Phoneme>)> {
+        self.tokenize_in(form, &Scheme::Native)
+    }
+
+    pub fn phonemes(&self) -> impl Iterator<Item = &Phoneme> {
+        self.vowels.iter().chain(self.consonants.iter())
+    }
+
+    // Every syllable pattern has exactly one vowel nucleus, so vowels = syllables.
+    pub fn count_syllables(&self, form: &str) -> usize {
+        self.segment(form).iter().filter(|p| p.is_vowel()).count()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -68,6 +232,21 @@ pub struct Morphology {
     // filter when we need prefixes/suffixes.
     #[serde(default)]
     pub derivational_rules: Vec<DerivationalRule>, // This replaces the old `affixes` field.
+    #[serde(default)]
+    pub homophony: HomophonyPolicy,
+    #[serde(default)]
+    pub max_derivations_per_pass: Option<usize>, // Caps how many new words a single pass may add.
+    #[serde(default)]
+    pub max_depth: Option<usize>,                // No lexeme is derived more than this many steps from its root.
+}
+
+// What to do when a derivation produces a form that already belongs to another word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+pub enum HomophonyPolicy {
+    Allow,          // Keep both words as separate lexemes that happen to sound the same.
+    #[default]
+    Reject,         // Drop the new derivation (but record it in the collision report).
+    RetryAllomorph, // Try the rule's `allomorphs` in order, dropping it only if all of them collide.
 }
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct SequenceRules {
@@ -85,14 +264,86 @@ pub struct LexiconGeneration {
     #[serde(default)]
     pub parts_of_speech: Vec<String>,
     #[serde(default)]
-    pub meanings: HashMap<String, Vec<String>>,
+    pub meanings: HashMap<String, Vec<Meaning>>,
+    #[serde(default)]
+    pub assignment: MeaningAssignment,
+    #[serde(default)]
+    pub allow_synonyms: bool, // In `Unique` mode, lets concepts be reused once every one has a root.
+    #[serde(default)]
+    pub meanings_from: Option<String>, // A built-in concept list, e.g. "swadesh-207"
+    #[serde(default)]
+    pub culture: Option<Culture>,      // Scales how many words each semantic domain gets.
+    #[serde(default)]
+    pub metaphors: Vec<ConceptualMetaphor>,
+    #[serde(default)]
+    pub drift: Option<SemanticDrift>,  // Meaning change over time, run once the lexicon is built.
+    #[serde(default)]
+    pub sound_changes: Vec<SoundChange>, // Regular sound changes, applied in order after drift.
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct Root {
-    pub form: String,
-    pub part_of_speech: String, // e.g., "noun", "verb", "adj"
-    pub meaning: String,          // e.g., "water", "to see", "big"
+impl LexiconGeneration {
+    // Merges the built-in list named by `meanings_from` into `meanings`. Entries written in
+    // the config win over a built-in entry with the same gloss, and any part of speech the
+    // list brings in is added to `parts_of_speech`.
+    pub fn resolve_meanings_from(&mut self) -> Result<(), ConfigError> {
+        let Some(name) = &self.meanings_from else {
+            return Ok(());
+        };
+        let builtin = wordlists::builtin_word_list(name)
+            .ok_or_else(|| ConfigError::UnknownWordList(name.clone()))?;
+
+        let mut builtin_pos: Vec<&String> = builtin.keys().collect();
+        builtin_pos.sort();
+        for pos in builtin_pos {
+            if !self.parts_of_speech.is_empty() && !self.parts_of_speech.contains(pos) {
+                self.parts_of_speech.push(pos.clone());
+            }
+            let custom = self.meanings.entry(pos.clone()).or_default();
+            for meaning in &builtin[pos] {
+                if !custom.iter().any(|m| m.gloss == meaning.gloss) {
+                    custom.push(meaning.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// How roots are given their meanings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+pub enum MeaningAssignment {
+    #[default]
+    Random, // Each root picks any meaning for its part of speech; repeats are possible.
+    Unique, // Each concept is given to exactly one root.
+}
+
+// A concept to coin a root for. In the JSON this is either a bare string ("stone")
+// or an object carrying semantic tags ({ "gloss": "stone", "tags": ["concrete", "mass"] }).
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(from = "MeaningSpec")]
+pub struct Meaning {
+    pub gloss: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MeaningSpec {
+    Plain(String),
+    Tagged {
+        gloss: String,
+        #[serde(default)]
+        tags: Vec<String>,
+    },
+}
+
+impl From<MeaningSpec> for Meaning {
+    fn from(spec: MeaningSpec) -> Self {
+        match spec {
+            MeaningSpec::Plain(gloss) => Self { gloss, tags: Vec::new() },
+            MeaningSpec::Tagged { gloss, tags } => Self { gloss, tags },
+        }
+    }
 }
 
 // An Affix now also has a meaning/function.
@@ -106,17 +357,58 @@ pub struct DerivationalRule {
     pub process: DerivationProcess,
     
     pub meaning_template: String,       // e.g., "great-{parent_meaning}"
+    #[serde(default)]
+    pub tagged_templates: Vec<TaggedTemplate>, // Checked in order before `meaning_template`.
+    #[serde(default)]
+    pub adds_tags: Vec<String>,         // Tags the output gains, e.g. ["location"]
+    #[serde(default)]
+    pub removes_tags: Vec<String>,      // Parent tags the output loses, e.g. ["animate"]
     #[serde(default)] // This makes the field optional in the JSON
     pub constraints: RuleConstraints,
+    #[serde(default)]
+    pub allomorphs: Vec<String>,        // Alternate affix forms, e.g. ["-ol", "-uln"]
+    #[serde(default = "default_productivity")]
+    pub productivity: f64,              // Chance (0.0 - 1.0) the rule fires on an eligible parent.
+    #[serde(default)]
+    pub domain: Option<String>,         // Only active when the culture finds this domain salient.
+}
+
+fn default_productivity() -> f64 { 1.0 }
+
+// A phrasing used instead of the rule's default template when the parent carries `tag`,
+// e.g. LocationOf + "emotion" -> "the brooding-place of {parent_meaning}".
+#[derive(Debug, Clone, Deserialize)]
+pub struct TaggedTemplate {
+    pub tag: String,
+    pub template: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct RuleConstraints {
     #[serde(default)]
     pub cannot_follow_rules: Vec<String>, // e.g., "LocationOf" can't follow "LocationOf"
-    // We could later add things like:
-    // pub cannot_be_followed_by: Vec<String>,
-    // pub required_parent_tags: Vec<String>,
+    #[serde(default)]
+    pub cannot_be_followed_by: Vec<String>, // Rules that may not apply to this rule's output.
+    #[serde(default)]
+    pub required_parent_tags: Vec<String>, // The parent must carry all of these semantic tags.
+    #[serde(default)]
+    pub excluded_parent_tags: Vec<String>, // The parent must carry none of these.
+    #[serde(default)]
+    pub parent_ends_with: Option<SoundClass>, // e.g., only parents ending in a vowel
+    #[serde(default)]
+    pub min_parent_syllables: Option<usize>,
+    #[serde(default)]
+    pub max_parent_syllables: Option<usize>,
+    #[serde(default)]
+    pub min_parent_depth: Option<usize>, // 0 = the parent is a root
+    #[serde(default)]
+    pub max_parent_depth: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum SoundClass {
+    Vowel,
+    Consonant,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -127,26 +419,128 @@ pub enum DerivationProcess {
     // We can add more later, like Infix or Compounding
 }
 
+impl DerivationProcess {
+    pub fn affix(&self) -> &str {
+        match self {
+            DerivationProcess::Prefix { form } | DerivationProcess::Suffix { form } => form,
+        }
+    }
+}
+
 // A complete, generated word with its full history.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Lexeme {
     pub id: Uuid,
     pub form: String,
     pub part_of_speech: String,
-    pub meaning: String,
+    pub senses: Vec<Sense>,           // Ordered; the first is the core meaning.
+    pub meaning_shifts: Vec<MeaningShift>, // How the core meaning drifted, oldest first.
+    pub form_changes: Vec<(FormChange, String)>, // What altered the form, oldest first, each with the form before it.
     
     // Graph-related fields
     pub parent_id: Option<Uuid>,      // Which lexeme did this derive from?
     pub rule_applied: Option<String>, // The name of the rule that created it.
+    pub affix_applied: Option<String>, // The affix actually attached (may be an allomorph).
+    pub origin: Origin,               // What kind of edge links this lexeme to its parent.
+    pub borrowed_from: Option<Loan>,  // Set for loanwords, whose parent lives in another language.
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Origin {
+    Root,       // Coined from scratch (generation 0).
+    Derivation, // Built from its parent by a derivational rule.
+    Idiom,      // A fixed phrase headed by its parent.
+    Loan,       // Borrowed from another language; see `Lexeme::borrowed_from`.
+}
+
+// The "borrowed from" edge of a loanword: the donor language and the word it was taken from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Loan {
+    pub language: String,
+    pub donor_id: Uuid,
+    pub donor_form: String,
+    pub donor_meaning: String,
+}
+
+// One meaning of a lexeme, with its own semantic tags (e.g. "concrete", "tool").
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sense {
+    pub gloss: String,
+    pub tags: Vec<String>,
+    pub source: SenseSource,
+}
+
+// Where a sense came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SenseSource {
+    Core,             // The meaning the word was coined with.
+    Derived,          // Built from the parent's meaning by a rule's template.
+    Metaphor(String), // Added by the named conceptual metaphor.
+    Drift,            // Shifted from an earlier sense over time.
+}
+
+impl Sense {
+    pub fn new(gloss: impl Into<String>, tags: Vec<String>, source: SenseSource) -> Self {
+        Self { gloss: gloss.into(), tags, source }
+    }
 }
 
+impl Lexeme {
+    // The core (first) sense's gloss.
+    pub fn meaning(&self) -> &str {
+        self.senses.first().map_or("", |s| s.gloss.as_str())
+    }
+
+    // The core sense's tags; these are what rules and constraints look at.
+    pub fn tags(&self) -> &[String] {
+        self.senses.first().map_or(&[], |s| s.tags.as_slice())
+    }
 
+    pub fn has_sense(&self, gloss: &str) -> bool {
+        self.senses.iter().any(|s| s.gloss == gloss)
+    }
+
+    // Every sense in order, e.g. "1. hall; 2. home". A single sense is shown bare.
+    pub fn gloss_list(&self) -> String {
+        if self.senses.len() == 1 {
+            return self.meaning().to_string();
+        }
+        self.senses.iter().enumerate()
+            .map(|(i, s)| format!("{}. {}", i + 1, s.gloss))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
 
-#[allow(dead_code)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Lexicon {
     // We use a HashMap to easily look up any lexeme by its ID.
     pub graph: HashMap<Uuid, Lexeme>,
-    pub roots: Vec<Uuid>, // A list of IDs for the "generation 0" root words.
+    pub roots: Vec<Uuid>, // A list of IDs for the "generation 0" native root words; loans are not listed.
+    pub collisions: Vec<FormCollision>, // Every derivation or loan that landed on an existing form.
+}
+
+// A derivation or loan whose form matched the form of a word already in the lexicon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormCollision {
+    pub form: String,
+    pub existing_ids: Vec<Uuid>,
+    pub parent_id: Option<Uuid>, // None for a loan, whose source lives in the donor's lexicon, or a replaced root.
+    pub rule_applied: String,    // For a loan, "borrowing from <language>"; for a root, "replacement of '<meaning>'".
+    pub resolution: CollisionResolution,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollisionResolution {
+    HomophoneAdded,
+    Rejected,
+    Allomorph(String), // The alternate affix that avoided the collision.
+}
+
+impl Default for Lexicon {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Lexicon {
@@ -154,11 +548,32 @@ impl Lexicon {
         Self {
             graph: HashMap::new(),
             roots: Vec::new(),
+            collisions: Vec::new(),
         }
     }
 
+    // Reads a lexicon written out with `to_json`, so several commands can work on the same words.
+    pub fn load(path: &str) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(ConfigError::FileRead)?;
+        serde_json::from_str(&contents).map_err(ConfigError::JsonParse)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a lexicon always serializes")
+    }
+
+    // Every form in the lexicon with the ids of the words that have it.
+    pub fn form_index(&self) -> HashMap<String, Vec<Uuid>> {
+        let mut form_to_ids: HashMap<String, Vec<Uuid>> = HashMap::new();
+        for lexeme in self.graph.values() {
+            form_to_ids.entry(lexeme.form.clone()).or_default().push(lexeme.id);
+        }
+        form_to_ids
+    }
+
+    // Adds a word to the graph; a native root (not a loan) is also listed in `roots`.
     pub fn add_lexeme(&mut self, lexeme: Lexeme) {
-        if lexeme.parent_id.is_none() {
+        if lexeme.parent_id.is_none() && lexeme.origin == Origin::Root {
             self.roots.push(lexeme.id);
         }
         self.graph.insert(lexeme.id, lexeme);
@@ -174,9 +589,18 @@ pub struct WordGenerator {
     pub lexicon_generation: LexiconGeneration,
     pub sequence_rules: SequenceRules,
     pub grammar: Grammar,
+    pub sound_symbolism: Option<SoundSymbolism>,
+    pub naming: Naming,
+    pub dialects: DialectSettings,
+    pub registers: RegisterSettings,
+    pub script: ScriptSettings,
+    pub pronunciation: PronunciationSettings,
+    pub speech: SpeechSettings,
+    pub texts: TextSettings,
 }
 
 impl WordGenerator {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         rules: Vec<SyllablePattern>,
         min: usize,
@@ -186,6 +610,14 @@ impl WordGenerator {
         lexicon_generation: LexiconGeneration,
         sequence_rules: SequenceRules,
         grammar: Grammar,
+        sound_symbolism: Option<SoundSymbolism>,
+        naming: Naming,
+        dialects: DialectSettings,
+        registers: RegisterSettings,
+        script: ScriptSettings,
+        pronunciation: PronunciationSettings,
+        speech: SpeechSettings,
+        texts: TextSettings,
     ) -> Self {
         Self {
             rules,
@@ -196,20 +628,37 @@ impl WordGenerator {
             lexicon_generation,
             sequence_rules,
             grammar,
+            sound_symbolism,
+            naming,
+            dialects,
+            registers,
+            script,
+            pronunciation,
+            speech,
+            texts,
         }
     }
 
-    fn generate_syllable_from_pattern(&self, inventory: &PhoneticInventory, pattern: &SyllablePattern) -> String {
+    fn generate_syllable_from_pattern(&self, inventory: &PhoneticInventory, pattern: &SyllablePattern, favoured: &[&str]) -> String {
+        let symbolism = self.sound_symbolism.as_ref().filter(|_| !favoured.is_empty());
         let mut syllable = String::new();
         for c in pattern.pattern.chars() {
             match c {
                 'C' => {
-                    if let Some(consonant) = inventory.get_random_consonant() { 
+                    let consonant = match symbolism {
+                        Some(s) => PhoneticInventory::get_weighted_phoneme(&inventory.consonants, |p| s.weight(p, favoured)),
+                        None => inventory.get_random_consonant(),
+                    };
+                    if let Some(consonant) = consonant { 
                         syllable.push_str(&consonant.grapheme); 
                     }
                 }
                 'V' => {
-                    if let Some(vowel) = inventory.get_random_vowel() { 
+                    let vowel = match symbolism {
+                        Some(s) => PhoneticInventory::get_weighted_phoneme(&inventory.vowels, |p| s.weight(p, favoured)),
+                        None => inventory.get_random_vowel(),
+                    };
+                    if let Some(vowel) = vowel { 
                         syllable.push_str(&vowel.grapheme); 
                     }
                 }
@@ -224,11 +673,31 @@ impl WordGenerator {
     }
 
     pub fn generate_root(&self, inventory: &PhoneticInventory) -> String {
+        self.generate_root_for(inventory, None)
+    }
+
+    // Like `generate_root`, but lets sound symbolism (if configured) see the meaning being
+    // coined: its tags bias phoneme choice, and onomatopoeic meanings may reduplicate.
+    pub fn generate_root_for(&self, inventory: &PhoneticInventory, meaning: Option<&Meaning>) -> String {
         let max_attempts = 100;
         let mut rng = rand::rng();
 
+        let favoured = match (&self.sound_symbolism, meaning) {
+            (Some(symbolism), Some(meaning)) => symbolism.favoured_features(meaning),
+            _ => Vec::new(),
+        };
+        let reduplicate = match (&self.sound_symbolism, meaning) {
+            (Some(symbolism), Some(meaning)) => symbolism.wants_reduplication(meaning)
+                && rng.random_bool(symbolism.reduplication_chance.clamp(0.0, 1.0)),
+            _ => false,
+        };
+
         for _ in 0..max_attempts {
-            let num_syllables = rng.random_range(self.min_syllables..=self.max_syllables);
+            let mut num_syllables = rng.random_range(self.min_syllables..=self.max_syllables);
+            if reduplicate {
+                // The base is copied, so build half the word (but at least one syllable).
+                num_syllables = num_syllables.div_ceil(2).max(1);
+            }
             let mut root_word = String::new();
             
             let mut consecutive_vowels = 0;
@@ -246,7 +715,7 @@ impl WordGenerator {
                     possible_rules.choose(&mut rng).unwrap()
                 };
 
-                root_word.push_str(&self.generate_syllable_from_pattern(inventory, chosen_rule));
+                root_word.push_str(&self.generate_syllable_from_pattern(inventory, chosen_rule, &favoured));
 
                 if chosen_rule.is_vowel_only() {
                     consecutive_vowels += 1;
@@ -255,6 +724,10 @@ impl WordGenerator {
                 }
             }
 
+            if reduplicate {
+                root_word = root_word.repeat(2);
+            }
+
             if !self.contains_illegal_patterns(&root_word) {
                 return root_word;
             }
@@ -266,70 +739,165 @@ impl WordGenerator {
     pub fn build_etymological_graph(&self, root_count: usize, inventory: &PhoneticInventory, derivation_passes: usize) -> Lexicon {
         let mut lexicon = Lexicon::new();
         let mut rng = rand::rng();
-        let mut form_to_id_map: HashMap<String, Uuid> = HashMap::new();
+        // Several lexemes may share a form (homophones), so a form maps to every id that bears it.
+        let mut form_to_ids: HashMap<String, Vec<Uuid>> = HashMap::new();
 
-        let mut used_forms = std::collections::HashSet::new();
-        while lexicon.roots.len() < root_count {
-            let form = self.generate_root(inventory);
-            if !used_forms.contains(&form) {
-                used_forms.insert(form.clone());
-                let part_of_speech = self.lexicon_generation.parts_of_speech.choose(&mut rng).unwrap().clone();
-                let meaning = self.lexicon_generation.meanings.get(&part_of_speech).and_then(|v| v.choose(&mut rng).cloned()).unwrap_or_default();
-                if !form_to_id_map.contains_key(&form){
-                    let root_lexeme = Lexeme {
-                    id: Uuid::new_v4(),
-                    form,
-                    part_of_speech,
-                    meaning,
-                    parent_id: None,
-                    rule_applied: None,
-                    };
+        // In `Unique` mode, roots draw from a shuffled pool of every concept until it runs dry.
+        let mut concept_pool: Vec<(String, Meaning)> = Vec::new();
+        let mut pool_exhausted = false;
+        let salience = self.lexicon_generation.culture.as_ref().map(|c| c.domain_salience());
+        let salience_of = |tags: &[String]| salience.as_ref().map_or(1.0, |s| s.of_tags(tags));
 
-                    form_to_id_map.insert(root_lexeme.form.clone(), root_lexeme.id);
-                    lexicon.add_lexeme(root_lexeme);
+        while lexicon.roots.len() < root_count {
+            // The meaning is picked first so sound symbolism can shape the form coined for it.
+            let (part_of_speech, meaning) = match self.lexicon_generation.assignment {
+                MeaningAssignment::Random => {
+                    let part_of_speech = self.lexicon_generation.parts_of_speech.choose(&mut rng).unwrap().clone();
+                    let meaning = self.lexicon_generation.meanings.get(&part_of_speech)
+                        .and_then(|v| v.choose_weighted(&mut rng, |m| salience_of(&m.tags)).ok().cloned());
+                    (part_of_speech, meaning)
                 }
-            }
+                MeaningAssignment::Unique => {
+                    if concept_pool.is_empty() {
+                        if pool_exhausted && !self.lexicon_generation.allow_synonyms {
+                            println!("Every concept has a root; stopping at {} roots (set allow_synonyms to go further).", lexicon.roots.len());
+                            break;
+                        }
+                        for (pos, meaning) in self.concepts() {
+                            // Irrelevant concepts may be skipped; salient ones get synonyms if allowed.
+                            let weight = salience_of(&meaning.tags);
+                            let copies = if weight < 1.0 {
+                                usize::from(rng.random_bool(weight.max(0.0)))
+                            } else if self.lexicon_generation.allow_synonyms {
+                                weight.round() as usize
+                            } else {
+                                1
+                            };
+                            concept_pool.extend(std::iter::repeat_n((pos, meaning.clone()), copies));
+                        }
+                        concept_pool.shuffle(&mut rng);
+                        pool_exhausted = true;
+                        if concept_pool.is_empty() {
+                            break;
+                        }
+                    }
+                    let (part_of_speech, meaning) = concept_pool.pop().unwrap();
+                    (part_of_speech, Some(meaning))
+                }
+            };
+
+            // A small syllable inventory can run out of fresh forms; stop rather than spin.
+            let max_attempts = 1000;
+            let Some(form) = (0..max_attempts)
+                .map(|_| self.generate_root_for(inventory, meaning.as_ref()))
+                .find(|form| !form_to_ids.contains_key(form))
+            else {
+                println!("No new root form found after {} attempts; stopping at {} roots (add syllable rules or phonemes to go further).", max_attempts, lexicon.roots.len());
+                break;
+            };
+            let (meaning, tags) = meaning.map(|m| (m.gloss, m.tags)).unwrap_or_default();
+            let root_lexeme = Lexeme {
+                id: Uuid::new_v4(),
+                form,
+                part_of_speech,
+                senses: vec![Sense::new(meaning, tags, SenseSource::Core)],
+                meaning_shifts: Vec::new(),
+                form_changes: Vec::new(),
+                parent_id: None,
+                rule_applied: None,
+                affix_applied: None,
+                origin: Origin::Root,
+                borrowed_from: None,
+            };
+
+            form_to_ids.entry(root_lexeme.form.clone()).or_default().push(root_lexeme.id);
+            lexicon.add_lexeme(root_lexeme);
         }
     
 
         let mut current_generation_ids: Vec<Uuid> = lexicon.roots.clone();
 
         for i in 0..derivation_passes {
+            // Every parent in this pass sits at depth `i`, so its children would sit at `i + 1`.
+            if self.morphology.max_depth.is_some_and(|max| i >= max) {
+                println!("\nMaximum derivation depth ({}) reached. Halting derivation.", i);
+                break;
+            }
+
             println!("\n--- Derivation Pass {} ---", i + 1);
             let mut next_generation_ids = Vec::new();
             let mut newly_derived_lexemes: Vec<Lexeme> = Vec::new();
+
+            // Shuffle so a per-pass cap doesn't always favour the same parents.
+            current_generation_ids.shuffle(&mut rng);
+            let pass_cap = self.morphology.max_derivations_per_pass.unwrap_or(usize::MAX);
             
             for parent_id in &current_generation_ids {
                 let parent_lexeme = lexicon.graph.get(parent_id).unwrap();
 
                 for rule in &self.morphology.derivational_rules {
-                    if rule.applies_to_pos.contains(&parent_lexeme.part_of_speech) {
+                    if next_generation_ids.len() >= pass_cap {
+                        break;
+                    }
+                    if rule.domain.as_ref().is_some_and(|d| !salience.as_ref().is_some_and(|s| s.is_salient(d))) {
+                        continue;
+                    }
+                    // Words in domains the culture cares about are derived from more eagerly.
+                    let chance = rule.productivity * salience_of(parent_lexeme.tags());
+                    if rule.applies_to_pos.contains(&parent_lexeme.part_of_speech)
+                        && rng.random_bool(chance.clamp(0.0, 1.0)) {
                         
-                        let mut is_constrained = false;
-                        if let Some(parent_rule_name) = &parent_lexeme.rule_applied {
-                            if rule.constraints.cannot_follow_rules.contains(parent_rule_name) {
-                                is_constrained = true;
-                            }
-                        }
+                        let is_constrained = !self.constraints_permit(rule, parent_lexeme, i, inventory);
                         if !is_constrained {
-                            let (new_form, new_pos, new_meaning) = Self::apply_rule(&parent_lexeme, rule);
-                            if !form_to_id_map.contains_key(&new_form) {
+                            let (new_form, new_pos, new_meaning) = Self::apply_rule(parent_lexeme, rule, rule.process.affix());
+                            let mut chosen = Some((new_form.clone(), rule.process.affix().to_string()));
+
+                            if let Some(existing_ids) = form_to_ids.get(&new_form) {
+                                let resolution = match self.morphology.homophony {
+                                    HomophonyPolicy::Allow => CollisionResolution::HomophoneAdded,
+                                    HomophonyPolicy::Reject => {
+                                        chosen = None;
+                                        CollisionResolution::Rejected
+                                    }
+                                    HomophonyPolicy::RetryAllomorph => {
+                                        chosen = rule.allomorphs.iter()
+                                            .map(|affix| (Self::apply_rule(parent_lexeme, rule, affix).0, affix.clone()))
+                                            .find(|(form, _)| !form_to_ids.contains_key(form));
+                                        match &chosen {
+                                            Some((_, affix)) => CollisionResolution::Allomorph(affix.clone()),
+                                            None => CollisionResolution::Rejected,
+                                        }
+                                    }
+                                };
+                                lexicon.collisions.push(FormCollision {
+                                    form: new_form.clone(),
+                                    existing_ids: existing_ids.clone(),
+                                    parent_id: Some(parent_lexeme.id),
+                                    rule_applied: rule.name.clone(),
+                                    resolution,
+                                });
+                            }
 
+                            if let Some((form, affix)) = chosen {
                                 let child_lexeme = Lexeme {
                                     id: Uuid::new_v4(),
-                                    form: new_form,
+                                    form,
                                     part_of_speech: new_pos,
-                                    meaning: new_meaning,
+                                    senses: vec![Sense::new(new_meaning, Self::derive_tags(parent_lexeme, rule), SenseSource::Derived)],
+                                    meaning_shifts: Vec::new(),
+                                    form_changes: Vec::new(),
                                     parent_id: Some(parent_lexeme.id),
                                     rule_applied: Some(rule.name.clone()),
+                                    affix_applied: Some(affix),
+                                    origin: Origin::Derivation,
+                                    borrowed_from: None,
                                 };
-                                form_to_id_map.insert(child_lexeme.form.clone(), child_lexeme.id);
+                                form_to_ids.entry(child_lexeme.form.clone()).or_default().push(child_lexeme.id);
                                 
-                                println!("  Derived '{}' ({}) from '{}' using rule '{}'", child_lexeme.form, child_lexeme.meaning, parent_lexeme.form, rule.name);
+                                println!("  Derived '{}' ({}) from '{}' using rule '{}'", child_lexeme.form, child_lexeme.meaning(), parent_lexeme.form, rule.name);
                                 next_generation_ids.push(child_lexeme.id);
                                 newly_derived_lexemes.push(child_lexeme);
                             }
-
                         };
 
                     }
@@ -348,13 +916,98 @@ impl WordGenerator {
             current_generation_ids = next_generation_ids;
         }
 
+        // Both leave their record on the lexemes (extra senses, idioms, meaning shifts).
+        self.apply_metaphors(&mut lexicon);
+        self.apply_drift(&mut lexicon);
+        for change in &self.lexicon_generation.sound_changes {
+            lexicon.apply_sound_change(inventory, change);
+        }
+
         lexicon
     }
 
-    fn apply_rule(parent: &Lexeme, rule: &DerivationalRule) -> (String, String, String) {
+    // Checks a new word that has no allomorphs to fall back on (a loan or an idiom) against
+    // `form_to_ids`. A clash goes in the collision report and follows the homophony policy;
+    // `RetryAllomorph` has nothing to retry with, so it refuses the word like `Reject`.
+    pub fn admits_form(&self, lexicon: &mut Lexicon, form_to_ids: &HashMap<String, Vec<Uuid>>, form: &str, parent_id: Option<Uuid>, rule_applied: String) -> bool {
+        let Some(existing_ids) = form_to_ids.get(form) else {
+            return true;
+        };
+        let resolution = match self.morphology.homophony {
+            HomophonyPolicy::Allow => CollisionResolution::HomophoneAdded,
+            HomophonyPolicy::Reject | HomophonyPolicy::RetryAllomorph => CollisionResolution::Rejected,
+        };
+        let admitted = resolution == CollisionResolution::HomophoneAdded;
+        lexicon.collisions.push(FormCollision {
+            form: form.to_string(),
+            existing_ids: existing_ids.clone(),
+            parent_id,
+            rule_applied,
+            resolution,
+        });
+        admitted
+    }
+
+    // Checks every constraint on `rule` (and the "cannot be followed by" list on the
+    // rule that produced the parent) against a parent sitting at `parent_depth`.
+    fn constraints_permit(&self, rule: &DerivationalRule, parent: &Lexeme, parent_depth: usize, inventory: &PhoneticInventory) -> bool {
+        let constraints = &rule.constraints;
+
+        if let Some(parent_rule_name) = &parent.rule_applied {
+            if constraints.cannot_follow_rules.contains(parent_rule_name) {
+                return false;
+            }
+            let parent_rule = self.morphology.derivational_rules.iter().find(|r| &r.name == parent_rule_name);
+            if parent_rule.is_some_and(|r| r.constraints.cannot_be_followed_by.contains(&rule.name)) {
+                return false;
+            }
+        }
+
+        if !constraints.required_parent_tags.iter().all(|t| parent.tags().contains(t))
+            || constraints.excluded_parent_tags.iter().any(|t| parent.tags().contains(t)) {
+            return false;
+        }
+
+        if constraints.min_parent_depth.is_some_and(|min| parent_depth < min)
+            || constraints.max_parent_depth.is_some_and(|max| parent_depth > max) {
+            return false;
+        }
+
+        if let Some(class) = constraints.parent_ends_with {
+            let ends_in_vowel = inventory.segment(&parent.form).last().is_some_and(|p| p.is_vowel());
+            if ends_in_vowel != (class == SoundClass::Vowel) {
+                return false;
+            }
+        }
+
+        if constraints.min_parent_syllables.is_some() || constraints.max_parent_syllables.is_some() {
+            let syllables = inventory.count_syllables(&parent.form);
+            if constraints.min_parent_syllables.is_some_and(|min| syllables < min)
+                || constraints.max_parent_syllables.is_some_and(|max| syllables > max) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn derive_tags(parent: &Lexeme, rule: &DerivationalRule) -> Vec<String> {
+        let mut tags: Vec<String> = parent.tags().iter()
+            .filter(|t| !rule.removes_tags.contains(t))
+            .cloned()
+            .collect();
+        for tag in &rule.adds_tags {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        tags
+    }
+
+    fn apply_rule(parent: &Lexeme, rule: &DerivationalRule, affix: &str) -> (String, String, String) {
     let new_form = match &rule.process {
-        DerivationProcess::Prefix { form } => format!("{}{}", form, parent.form),
-        DerivationProcess::Suffix { form } => format!("{}{}", parent.form, form),
+        DerivationProcess::Prefix { .. } => format!("{}{}", affix, parent.form),
+        DerivationProcess::Suffix { .. } => format!("{}{}", parent.form, affix),
     };
 
     let new_pos = if rule.output_pos == "SameAsInput" {
@@ -363,43 +1016,34 @@ impl WordGenerator {
         rule.output_pos.clone()
     };
     
-    let new_meaning = rule.meaning_template.replace("{parent_meaning}", &parent.meaning);
+    let template = rule.tagged_templates.iter()
+        .find(|t| parent.tags().contains(&t.tag))
+        .map_or(&rule.meaning_template, |t| &t.template);
+    let new_meaning = template.replace("{parent_meaning}", parent.meaning());
 
     (new_form, new_pos, new_meaning)
     }
 
-    pub fn generate_sentence(&self, lexicon: &Lexicon) -> String {
-        let mut rng = rand::rng();
-
-        // Find a random noun for the subject
-        let subject = lexicon.graph.values()
-            .filter(|l| l.part_of_speech == "noun")
-            .choose(&mut rng)
-            .map_or("<noun>", |l| &l.form);
+    // A sentence in a register picked by `registers.weights`.
+    pub fn generate_sentence(&self, inventory: &PhoneticInventory, lexicon: &Lexicon) -> (Register, String) {
+        let register = self.pick_register();
+        (register, self.generate_sentence_in(inventory, lexicon, register))
+    }
 
-        // Find a random verb
-        let verb = lexicon.graph.values()
-            .filter(|l| l.part_of_speech == "verb")
+    pub fn generate_sentence_in(&self, inventory: &PhoneticInventory, lexicon: &Lexicon, register: Register) -> String {
+        let mut rng = rand::rng();
+        let mut pick = |pos: &str| lexicon.graph.values()
+            .filter(|l| l.part_of_speech == pos)
             .choose(&mut rng)
-            .map_or("<verb>", |l| &l.form);
+            .map(|l| self.register_variant(inventory, l, register).form);
 
-        // Find a random noun for the object
-        let object = lexicon.graph.values()
-            .filter(|l| l.part_of_speech == "noun")
-            .choose(&mut rng)
-            .map_or("<noun>", |l| &l.form);
+        // Find a random noun for the subject, a verb, and a noun for the object
+        let subject = pick("noun").unwrap_or_else(|| "<noun>".to_string());
+        let verb = pick("verb").unwrap_or_else(|| "<verb>".to_string());
+        let object = pick("noun").unwrap_or_else(|| "<noun>".to_string());
 
         // Arrange them based on the grammar rule
-        let sentence = match self.grammar.word_order.as_str() {
-            "SVO" => format!("{} {} {}", subject, verb, object),
-            "SOV" => format!("{} {} {}", subject, object, verb),
-            "VSO" => format!("{} {} {}", verb, subject, object),
-            "VOS" => format!("{} {} {}", verb, object, subject),
-            "OSV" => format!("{} {} {}", object, subject, verb),
-            "OVS" => format!("{} {} {}", object, verb, subject),
-
-            _ => format!("{} {} {}", subject, verb, object), // Default to SVO
-        };
+        let sentence = self.grammar.arrange(subject, verb, object).join(" ");
         
         // Capitalize first letter and add a period.
         let mut c = sentence.chars();
@@ -429,6 +1073,22 @@ pub struct LanguageConfig {
     pub lexicon_generation: LexiconGeneration,
     #[serde(default)]
     pub grammar: Grammar,
+    #[serde(default)]
+    pub sound_symbolism: Option<SoundSymbolism>,
+    #[serde(default)]
+    pub naming: Naming,
+    #[serde(default)]
+    pub dialects: DialectSettings,
+    #[serde(default)]
+    pub registers: RegisterSettings,
+    #[serde(default)]
+    pub script: ScriptSettings,
+    #[serde(default)]
+    pub pronunciation: PronunciationSettings,
+    #[serde(default)]
+    pub speech: SpeechSettings,
+    #[serde(default)]
+    pub texts: TextSettings,
 }
 
 pub fn initialize_from_config(config_path: &str) -> Result<(PhoneticInventory, WordGenerator), ConfigError> {
@@ -436,22 +1096,46 @@ pub fn initialize_from_config(config_path: &str) -> Result<(PhoneticInventory, W
     let mut contents = String::new();
     file.read_to_string(&mut contents).map_err(ConfigError::FileRead)?;
 
-    let config: LanguageConfig = serde_json::from_str(&contents).map_err(ConfigError::JsonParse)?;
+    let mut config: LanguageConfig = serde_json::from_str(&contents).map_err(ConfigError::JsonParse)?;
+    config.lexicon_generation.resolve_meanings_from()?;
 
-    let inventory = PhoneticInventory::new(config.phonemes);
-    let rules = config.syllable_rules.iter().map(|r| SyllablePattern::new(r)).collect::<Vec<SyllablePattern>>();
-    let generator = WordGenerator::new(
-        rules.clone(),
-        config.min_syllables,
-        config.max_syllables,
-        config.illegal_patterns,
-        config.morphology,
-        config.lexicon_generation,
-        config.sequence_rules,
-        config.grammar,
-    );
+    // Files a config points at are found next to it, not in the working directory.
+    let config_dir = Path::new(config_path).parent().unwrap_or(Path::new(""));
+    config.dialects.map = config.dialects.map.map(|path| resolve_relative(config_dir, &path));
+    config.texts.history = config.texts.history.map(|path| resolve_relative(config_dir, &path));
+
+    Ok(config.into_generator())
+}
 
-    Ok((inventory, generator))
+impl LanguageConfig {
+    pub fn into_generator(self) -> (PhoneticInventory, WordGenerator) {
+        let inventory = PhoneticInventory::new(self.phonemes);
+        let rules = self.syllable_rules.iter().map(|r| SyllablePattern::new(r)).collect::<Vec<SyllablePattern>>();
+        let generator = WordGenerator::new(
+            rules,
+            self.min_syllables,
+            self.max_syllables,
+            self.illegal_patterns,
+            self.morphology,
+            self.lexicon_generation,
+            self.sequence_rules,
+            self.grammar,
+            self.sound_symbolism,
+            self.naming,
+            self.dialects,
+            self.registers,
+            self.script,
+            self.pronunciation,
+            self.speech,
+            self.texts,
+        );
+        (inventory, generator)
+    }
+}
+
+// `path` as written in a config, made relative to the config's directory unless it is absolute.
+fn resolve_relative(config_dir: &Path, path: &str) -> String {
+    config_dir.join(path).to_string_lossy().into_owned()
 }
 
 pub fn export_to_dot(lexicon: &Lexicon) -> String {
@@ -465,10 +1149,14 @@ pub fn export_to_dot(lexicon: &Lexicon) -> String {
             "\"{} [{}]\\n'{}'\"", // Format: "form [pos]\n'meaning'"
             lexeme.form.replace('"', "\\\""), // Escape quotes
             lexeme.part_of_speech,
-            lexeme.meaning.replace('"', "\\\"")
+            lexeme.gloss_list().replace('"', "\\\"")
         );
         
-        let color = if lexeme.parent_id.is_none() { "lightblue" } else { "lightgray" };
+        let color = match (lexeme.origin, lexeme.parent_id) {
+            (Origin::Loan, _) => "khaki",
+            (_, None) => "lightblue",
+            _ => "lightgray",
+        };
 
         dot_string.push_str(&format!(
             "  \"{}\" [label={}, style=filled, fillcolor={}];\n",
@@ -476,19 +1164,151 @@ pub fn export_to_dot(lexicon: &Lexicon) -> String {
         ));
     }
 
-    dot_string.push_str("\n");
+    dot_string.push('\n');
 
     // Second, define all the edges (relationships)
     for (id, lexeme) in &lexicon.graph {
         if let Some(parent_id) = lexeme.parent_id {
             let rule_label = lexeme.rule_applied.as_deref().unwrap_or("");
+            // Semantic extensions are drawn dashed to set them apart from morphology.
+            let style = match lexeme.origin {
+                Origin::Idiom => ", style=dashed",
+                Origin::Root | Origin::Derivation | Origin::Loan => "",
+            };
             dot_string.push_str(&format!(
-                "  \"{}\" -> \"{}\" [label=\"{}\"];\n",
-                parent_id, id, rule_label
+                "  \"{}\" -> \"{}\" [label=\"{}\"{}];\n",
+                parent_id, id, rule_label, style
             ));
         }
     }
 
     dot_string.push_str("}\n");
     dot_string
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{derived, generator, inventory, root};
+    use serde_json::{json, Value};
+
+    // One noun concept, so `build_etymological_graph(1, ..)` coins exactly one root, and
+    // `rules` in order with the morphology `settings` on top.
+    fn one_root_generator(rules: Value, settings: Value) -> WordGenerator {
+        let mut morphology = json!({ "derivational_rules": rules });
+        if let (Some(morphology), Value::Object(settings)) = (morphology.as_object_mut(), settings) {
+            morphology.extend(settings);
+        }
+        generator(json!({
+            "lexicon_generation": { "meanings": { "noun": ["water"] }, "assignment": "Unique" },
+            "morphology": morphology,
+        }))
+    }
+
+    fn suffix(name: &str, form: &str, allomorphs: &[&str]) -> Value {
+        json!({
+            "name": name,
+            "type": "Suffix",
+            "form": form,
+            "allomorphs": allomorphs,
+            "applies_to_pos": ["noun"],
+            "output_pos": "noun",
+            "meaning_template": format!("{} of {{parent_meaning}}", name.to_lowercase()),
+        })
+    }
+
+    #[test]
+    fn a_derivation_that_sounds_like_a_word_is_rejected_by_default() {
+        let generator = one_root_generator(json!([suffix("Place", "-ka", &[]), suffix("Field", "-ka", &[])]), json!({}));
+        let lexicon = generator.build_etymological_graph(1, &inventory(), 1);
+
+        assert_eq!(lexicon.graph.len(), 2);
+        assert_eq!(lexicon.collisions.len(), 1);
+        let collision = &lexicon.collisions[0];
+        assert_eq!(collision.resolution, CollisionResolution::Rejected);
+        assert_eq!(collision.rule_applied, "Field");
+        assert_eq!(collision.parent_id, Some(lexicon.roots[0]));
+    }
+
+    #[test]
+    fn retry_allomorph_falls_back_to_the_first_allomorph_that_is_free() {
+        let rules = json!([suffix("Place", "-ka", &[]), suffix("Field", "-ka", &["-ka", "-ku"])]);
+        let generator = one_root_generator(rules, json!({ "homophony": "RetryAllomorph" }));
+        let lexicon = generator.build_etymological_graph(1, &inventory(), 1);
+
+        assert_eq!(lexicon.collisions[0].resolution, CollisionResolution::Allomorph("-ku".to_string()));
+        let field = lexicon.graph.values().find(|l| l.rule_applied.as_deref() == Some("Field")).unwrap();
+        assert_eq!(field.affix_applied.as_deref(), Some("-ku"));
+        assert_eq!(field.form, format!("{}-ku", lexicon.graph[&lexicon.roots[0]].form));
+    }
+
+    #[test]
+    fn allow_keeps_both_homophones() {
+        let rules = json!([suffix("Place", "-ka", &[]), suffix("Field", "-ka", &[])]);
+        let generator = one_root_generator(rules, json!({ "homophony": "Allow" }));
+        let lexicon = generator.build_etymological_graph(1, &inventory(), 1);
+
+        assert_eq!(lexicon.graph.len(), 3);
+        assert_eq!(lexicon.collisions[0].resolution, CollisionResolution::HomophoneAdded);
+    }
+
+    #[test]
+    fn derivation_stops_at_max_depth() {
+        let generator = one_root_generator(json!([suffix("Place", "-ka", &[])]), json!({ "max_depth": 2 }));
+        let lexicon = generator.build_etymological_graph(1, &inventory(), 5);
+
+        assert_eq!(lexicon.graph.len(), 3);
+        assert!(lexicon.graph.keys().all(|id| lexicon.depth_of(id).unwrap() <= 2));
+    }
+
+    #[test]
+    fn a_pass_adds_no_more_words_than_its_cap() {
+        let generator = generator(json!({
+            "lexicon_generation": { "meanings": { "noun": ["water", "stone", "fire"] }, "assignment": "Unique" },
+            "morphology": { "derivational_rules": [suffix("Place", "-ka", &[])], "max_derivations_per_pass": 2 },
+        }));
+        let lexicon = generator.build_etymological_graph(3, &inventory(), 1);
+
+        assert_eq!(lexicon.roots.len(), 3);
+        assert_eq!(lexicon.graph.len(), 5);
+    }
+
+    #[test]
+    fn an_unproductive_rule_never_fires() {
+        let mut rule = suffix("Place", "-ka", &[]);
+        rule["productivity"] = json!(0.0);
+        let lexicon = one_root_generator(json!([rule]), json!({})).build_etymological_graph(1, &inventory(), 3);
+
+        assert_eq!(lexicon.graph.len(), 1);
+    }
+
+    #[test]
+    fn rule_constraints_check_tags_shape_depth_and_the_rule_before() {
+        let mut place = suffix("Place", "-ka", &[]);
+        place["constraints"] = json!({
+            "required_parent_tags": ["concrete"],
+            "excluded_parent_tags": ["animate"],
+            "parent_ends_with": "Vowel",
+            "max_parent_syllables": 2,
+            "max_parent_depth": 1,
+            "cannot_follow_rules": ["Place"],
+        });
+        let mut small = suffix("Small", "-ug", &[]);
+        small["constraints"] = json!({ "cannot_be_followed_by": ["Place"] });
+        let generator = one_root_generator(json!([place, small]), json!({}));
+        let (inventory, rule) = (inventory(), &generator.morphology.derivational_rules[0]);
+        let permits = |parent: &Lexeme, depth| generator.constraints_permit(rule, parent, depth, &inventory);
+
+        let stone = root("tora", "noun", "stone", &["concrete"]);
+        assert!(permits(&stone, 0));
+        assert!(!permits(&root("tora", "noun", "idea", &[]), 0));
+        assert!(!permits(&root("tora", "noun", "ox", &["concrete", "animate"]), 0));
+        assert!(!permits(&root("tor", "noun", "stone", &["concrete"]), 0));
+        assert!(!permits(&root("tokora", "noun", "stone", &["concrete"]), 0));
+        assert!(!permits(&stone, 2));
+        // Shaped like `stone`, so only the rule that made them stands in the way.
+        let after = |rule: &str| Lexeme { senses: stone.senses.clone(), ..derived(&stone, "tora", "stone", rule, "") };
+        assert!(permits(&after("Other"), 1));
+        assert!(!permits(&after("Place"), 1));
+        assert!(!permits(&after("Small"), 1));
+    }
+}
@@ -1,5 +1,9 @@
-use genesis_engine_lexicon::{initialize_from_config};
+use genesis_engine_lexicon::{initialize_from_config, CollisionResolution, FontMapping, FormMatch, Lexicon, LexiconQuery, NameKind, Origin, PhoneticInventory, Register, Scheme, ScriptKind, SenseSource, SortOrder, TextKind, WordGenerator};
+use genesis_engine_lexicon::historical_text;
+use clap::ValueEnum;
 use clap::{Parser, Subcommand};
+use regex::Regex;
+use std::path::Path;
 
 
 #[derive(Parser, Debug)]
@@ -17,9 +21,17 @@ enum Commands {
         #[arg(short, long)]
         lang: String,
 
-        /// Number of words to generate
-        #[arg(short, long, default_value_t = 20)]
-        count: usize,
+        /// Number of root words to generate (defaults to one per concept in the language file)
+        #[arg(short, long)]
+        count: Option<usize>,
+
+        /// Spelling to show forms in: native, ipa, or a romanization (scholarly, ascii, popular, ...)
+        #[arg(long, default_value = "native")]
+        scheme: String,
+
+        /// Save the lexicon to this JSON file, for lookup, etymology and name to reuse with --lexicon
+        #[arg(long)]
+        save: Option<String>,
     },
     /// Validate the syntax of a language configuration file
     Validate {
@@ -45,6 +57,10 @@ enum Commands {
         /// Output file path for the .dot file
         #[arg(short, long, default_value = "lexicon.dot")]
         output: String,
+
+        /// Spelling to show forms in: native, ipa, or a romanization (scholarly, ascii, popular, ...)
+        #[arg(long, default_value = "native")]
+        scheme: String,
     },
 
     /// Generate sample sentences from a language
@@ -64,34 +80,519 @@ enum Commands {
         /// Number of sentences to generate
         #[arg(short, long, default_value_t = 5)]
         num: usize,
+
+        /// Speak every sentence in this register instead of a random one each time
+        #[arg(short, long, value_enum)]
+        register: Option<RegisterArg>,
+    },
+
+    /// Search a generated lexicon like a bilingual dictionary
+    Lookup {
+        /// Path to the language JSON file
+        #[arg(short, long)]
+        lang: String,
+
+        /// Number of root words to build the lexicon with
+        #[arg(long, default_value_t = 50)]
+        roots: usize,
+
+        /// Number of derivation passes to run
+        #[arg(long, default_value_t = 2)]
+        passes: usize,
+
+        /// Match the conlang form exactly
+        #[arg(long, conflicts_with_all = ["prefix", "regex"])]
+        form: Option<String>,
+
+        /// Match conlang forms starting with this prefix
+        #[arg(long, conflicts_with = "regex")]
+        prefix: Option<String>,
+
+        /// Match conlang forms against a regular expression
+        #[arg(long)]
+        regex: Option<String>,
+
+        /// Match English glosses containing this text (reverse dictionary)
+        #[arg(short, long)]
+        meaning: Option<String>,
+
+        /// Only show this part of speech
+        #[arg(long)]
+        pos: Option<String>,
+
+        /// Only show words derived by this rule
+        #[arg(long)]
+        rule: Option<String>,
+
+        /// Only show words at least this many derivations from their root
+        #[arg(long)]
+        min_depth: Option<usize>,
+
+        /// Only show words at most this many derivations from their root
+        #[arg(long)]
+        max_depth: Option<usize>,
+
+        /// Sort results by conlang form or by English gloss
+        #[arg(short, long, value_enum, default_value_t = SortArg::Form)]
+        sort: SortArg,
+
+        /// Spelling to show forms in: native, ipa, or a romanization (scholarly, ascii, popular, ...)
+        #[arg(long, default_value = "native")]
+        scheme: String,
+
+        /// Saved lexicon (from generate --save) to use instead of building a new one
+        #[arg(long)]
+        lexicon: Option<String>,
+    },
+
+    /// Trace the history of words back to their roots
+    Etymology {
+        /// Path to the language JSON file
+        #[arg(short, long)]
+        lang: String,
+
+        /// Number of root words to build the lexicon with
+        #[arg(long, default_value_t = 20)]
+        roots: usize,
+
+        /// Number of derivation passes to run
+        #[arg(long, default_value_t = 2)]
+        passes: usize,
+
+        /// The word to trace; traces every derived or drifted word when omitted
+        #[arg(short, long)]
+        word: Option<String>,
+
+        /// Saved lexicon (from generate --save) to use instead of building a new one
+        #[arg(long)]
+        lexicon: Option<String>,
+    },
+
+    /// Generate names for people, places and polities
+    Name {
+        /// Path to the language JSON file
+        #[arg(short, long)]
+        lang: String,
+
+        /// Number of root words to build the lexicon with
+        #[arg(long, default_value_t = 50)]
+        roots: usize,
+
+        /// Number of derivation passes to run
+        #[arg(long, default_value_t = 2)]
+        passes: usize,
+
+        /// Only use templates for this kind of name
+        #[arg(short, long, value_enum)]
+        kind: Option<KindArg>,
+
+        /// Only use the template with this name
+        #[arg(short, long)]
+        template: Option<String>,
+
+        /// Number of names to generate
+        #[arg(short, long, default_value_t = 10)]
+        num: usize,
+
+        /// Saved lexicon (from generate --save) to use instead of building a new one
+        #[arg(long)]
+        lexicon: Option<String>,
+    },
+
+    /// Borrow words for some semantic domains from another language
+    Borrow {
+        /// Path to the recipient language JSON file
+        #[arg(short, long)]
+        lang: String,
+
+        /// Path to the donor language JSON file
+        #[arg(short, long)]
+        donor: String,
+
+        /// Name of the donor language in etymologies (defaults to the donor file's name)
+        #[arg(long)]
+        donor_name: Option<String>,
+
+        /// Semantic domain (meaning tag) to borrow words for; may be repeated
+        #[arg(short = 'm', long = "domain", required = true)]
+        domains: Vec<String>,
+
+        /// Number of root words to build each lexicon with
+        #[arg(long, default_value_t = 50)]
+        roots: usize,
+
+        /// Number of derivation passes to run
+        #[arg(long, default_value_t = 2)]
+        passes: usize,
+    },
+
+    /// Generate a writing system and export its glyphs as SVG
+    Script {
+        /// Path to the language JSON file
+        #[arg(short, long)]
+        lang: String,
+
+        /// Kind of script (overrides the language file)
+        #[arg(short, long, value_enum)]
+        kind: Option<ScriptArg>,
+
+        /// Seed the glyph shapes are drawn from (overrides the language file)
+        #[arg(short, long)]
+        seed: Option<u64>,
+
+        /// Directory to write the glyphs, sign chart and mapping table into
+        #[arg(short, long, default_value = "script")]
+        output: String,
+    },
+
+    /// Write words or sentences in the language's script and render them as SVG
+    Write {
+        /// Path to the language JSON file
+        #[arg(short, long)]
+        lang: String,
+
+        /// Text to write; if omitted, sentences are generated from a fresh lexicon
+        text: Option<String>,
+
+        /// Number of root words to build the lexicon with
+        #[arg(long, default_value_t = 50)]
+        roots: usize,
+
+        /// Number of derivation passes to run
+        #[arg(long, default_value_t = 2)]
+        passes: usize,
+
+        /// Number of sentences to generate when no text is given
+        #[arg(short, long, default_value_t = 3)]
+        num: usize,
+
+        /// Path to save the rendered SVG
+        #[arg(short, long, default_value = "writing.svg")]
+        output: String,
+    },
+
+    /// Export the language's script as a TrueType font with a specimen sheet
+    Font {
+        /// Path to the language JSON file
+        #[arg(short, long)]
+        lang: String,
+
+        /// Characters the glyphs are typed with
+        #[arg(short, long, value_enum, default_value_t = MappingArg::PrivateUse)]
+        mapping: MappingArg,
+
+        /// Font family name
+        #[arg(short, long, default_value = "Genesis Script")]
+        family: String,
+
+        /// Number of root words to build the sample text's lexicon with
+        #[arg(long, default_value_t = 40)]
+        roots: usize,
+
+        /// Number of sample sentences in the specimen
+        #[arg(short, long, default_value_t = 3)]
+        num: usize,
+
+        /// Directory to write the font, specimen and code point table into
+        #[arg(short, long, default_value = "font")]
+        output: String,
+    },
+
+    /// Split a language into regional dialects across its speech communities
+    Dialects {
+        /// Path to the language JSON file
+        #[arg(short, long)]
+        lang: String,
+
+        /// Number of root words to build the proto-language with
+        #[arg(long, default_value_t = 30)]
+        roots: usize,
+
+        /// Number of derivation passes to run
+        #[arg(long, default_value_t = 1)]
+        passes: usize,
+
+        /// Number of time steps to simulate (overrides the language file)
+        #[arg(short, long)]
+        steps: Option<usize>,
+
+        /// Number of root words to compare across communities
+        #[arg(short, long, default_value_t = 12)]
+        words: usize,
+
+        /// Directory to write one .dot lexicon per community into
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// GeoJSON or CSV map of communities (overrides the language file)
+        #[arg(long)]
+        map: Option<String>,
+    },
+
+    /// Print the pronunciation key and "sounds like" guides for English readers
+    Pronounce {
+        /// Path to the language JSON file
+        #[arg(short, long)]
+        lang: String,
+
+        /// Words to give pronunciation guides for
+        words: Vec<String>,
+
+        /// Number of root words to build the dictionary with
+        #[arg(long, default_value_t = 50)]
+        roots: usize,
+
+        /// Number of derivation passes to run
+        #[arg(long, default_value_t = 2)]
+        passes: usize,
+
+        /// Write a Markdown dictionary with the key and every word's pronunciation
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Speak words and sentences into WAV files with a formant synthesiser
+    Speak {
+        /// Path to the language JSON file
+        #[arg(short, long)]
+        lang: String,
+
+        /// Text to speak (speaks words and sentences from a generated lexicon if omitted)
+        text: Option<String>,
+
+        /// Number of root words to build the lexicon with
+        #[arg(long, default_value_t = 30)]
+        roots: usize,
+
+        /// Number of derivation passes to run
+        #[arg(long, default_value_t = 1)]
+        passes: usize,
+
+        /// Number of words to speak
+        #[arg(short, long, default_value_t = 5)]
+        words: usize,
+
+        /// Number of sentences to speak
+        #[arg(short, long, default_value_t = 3)]
+        num: usize,
+
+        /// Directory to write the WAV files into
+        #[arg(short, long, default_value = "speech")]
+        output: String,
+    },
+
+    /// Write a decree, inscription or chronicle with an interlinear translation
+    Text {
+        /// Path to the language JSON file
+        #[arg(short, long)]
+        lang: String,
+
+        /// Kind of text to write
+        #[arg(short, long, value_enum, default_value_t = TextArg::Chronicle)]
+        kind: TextArg,
+
+        /// Only use the text template with this name
+        #[arg(short, long)]
+        template: Option<String>,
+
+        /// JSON history log to take events from (overrides the language file)
+        #[arg(long)]
+        history: Option<String>,
+
+        /// Number of events to make up when there is no history log
+        #[arg(short, long, default_value_t = 4)]
+        events: usize,
+
+        /// Number of root words to build the lexicon with
+        #[arg(long, default_value_t = 60)]
+        roots: usize,
+
+        /// Number of derivation passes to run
+        #[arg(long, default_value_t = 2)]
+        passes: usize,
+
+        /// Save the text and its interlinear translation to this file
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Respell text from one spelling scheme into another
+    Convert {
+        /// Path to the language JSON file
+        #[arg(short, long)]
+        lang: String,
+
+        /// Scheme the text is written in
+        #[arg(long, default_value = "native")]
+        from: String,
+
+        /// Scheme to respell it into
+        #[arg(long, default_value = "ipa")]
+        to: String,
+
+        /// Text to convert (lists every scheme's spelling of each phoneme if omitted)
+        text: Option<String>,
     },
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum KindArg {
+    Person,
+    Place,
+    Polity,
+    Clan,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum RegisterArg {
+    Formal,
+    Common,
+    Slang,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ScriptArg {
+    Alphabet,
+    Abjad,
+    Abugida,
+    Syllabary,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum MappingArg {
+    PrivateUse,
+    Latin,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum TextArg {
+    Decree,
+    Inscription,
+    Chronicle,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum SortArg {
+    Form,
+    Gloss,
+}
+
+// Looks up a --scheme name, listing the ones the language has if it is unknown.
+fn resolve_scheme(inventory: &PhoneticInventory, name: &str) -> Option<Scheme> {
+    let scheme = inventory.scheme(name);
+    if scheme.is_none() {
+        let names: Vec<String> = inventory.schemes().iter().map(|s| s.name().to_string()).collect();
+        eprintln!("\n❌ Error: Unknown spelling scheme '{}' (available: {})", name, names.join(", "));
+    }
+    scheme
+}
+
+// The lexicon saved at `saved` (see `generate --save`), or else a newly built one.
+fn load_or_build(generator: &WordGenerator, inventory: &PhoneticInventory, saved: Option<&String>, roots: usize, passes: usize) -> Option<Lexicon> {
+    let Some(path) = saved else {
+        return Some(generator.build_etymological_graph(roots, inventory, passes));
+    };
+    match Lexicon::load(path) {
+        Ok(lexicon) => {
+            println!("Read {} words from: {}", lexicon.graph.len(), path);
+            Some(lexicon)
+        }
+        Err(e) => {
+            eprintln!("\n❌ Error: Could not read the lexicon '{}'.", path);
+            eprintln!("Reason: {}", e);
+            None
+        }
+    }
+}
 
 fn main() {
     let cli = Cli::parse();
 
     match &cli.command {
-        Commands::Generate { lang, count } => {
+        Commands::Generate { lang, count, scheme, save } => {
             println!("--- Genesis Engine: Morphological Engine ---");
             println!("Loading language from: {}", lang);
             
             match initialize_from_config(lang) {
                 Ok((inventory, generator)) => {
+                    let Some(scheme) = resolve_scheme(&inventory, scheme) else { return };
+                    let count = count.unwrap_or_else(|| generator.concept_count());
+
                     // Generate the entire graph with 2 derivation passes.
-                    let lexicon = generator.build_etymological_graph(*count, &inventory, 2);
+                    let native = generator.build_etymological_graph(count, &inventory, 2);
+                    if let Some(path) = save {
+                        match std::fs::write(path, native.to_json()) {
+                            Ok(()) => println!("\nSaved the lexicon to: {}", path),
+                            Err(e) => eprintln!("\n❌ Error: Could not write '{}': {}", path, e),
+                        }
+                    }
+                    let lexicon = native.respelled(&inventory, &scheme);
 
                     println!("\n--- Final Lexicon ({} total words) ---", lexicon.graph.len());
-                    for (id, lexeme) in &lexicon.graph {
-                        if lexeme.parent_id.is_none() {
-                            println!("[ROOT] {}: {} ({})", lexeme.form, lexeme.meaning, lexeme.part_of_speech);
-                        } else {
-                            let parent = lexicon.graph.get(&lexeme.parent_id.unwrap()).unwrap();
-                            println!("[DERIVED] {}: {} ({}) <-- from '{}' via '{}'", lexeme.form, lexeme.meaning, lexeme.part_of_speech, parent.form, lexeme.rule_applied.as_ref().unwrap());
+                    for lexeme in lexicon.graph.values() {
+                        match lexeme.parent_id {
+                            None => {
+                                println!("[ROOT] {}: {} ({})", lexeme.form, lexeme.gloss_list(), lexeme.part_of_speech);
+                            }
+                            Some(parent_id) => {
+                                let parent = lexicon.graph.get(&parent_id).unwrap();
+                                let label = match lexeme.origin {
+                                    Origin::Idiom => "[IDIOM]",
+                                    Origin::Root | Origin::Derivation | Origin::Loan => "[DERIVED]",
+                                };
+                                println!("{} {}: {} ({}) <-- from '{}' via '{}'", label, lexeme.form, lexeme.gloss_list(), lexeme.part_of_speech, parent.form, lexeme.rule_applied.as_ref().unwrap());
+                            }
+                        }
+                    }
+
+                    let mut extended: Vec<(&str, &str, &str)> = lexicon.graph.values()
+                        .flat_map(|l| l.senses.iter().filter_map(move |sense| match &sense.source {
+                            SenseSource::Metaphor(name) => Some((l.form.as_str(), sense.gloss.as_str(), name.as_str())),
+                            _ => None,
+                        }))
+                        .collect();
+                    if !extended.is_empty() {
+                        extended.sort();
+                        println!("\n--- Metaphorical Extensions ({} senses) ---", extended.len());
+                        for (form, gloss, metaphor) in extended {
+                            println!("{}: {} ({})", form, gloss, metaphor);
+                        }
+                    }
+
+                    let mut drifted: Vec<_> = lexicon.graph.values()
+                        .filter_map(|l| l.meaning_history().map(|history| (l.form.as_str(), history)))
+                        .collect();
+                    if !drifted.is_empty() {
+                        drifted.sort();
+                        println!("\n--- Semantic Drift ({} words) ---", drifted.len());
+                        for (form, history) in drifted {
+                            println!("{}: {}", form, history);
                         }
                     }
+
+                    if !lexicon.collisions.is_empty() {
+                        println!("\n--- Collision Report ({} collisions) ---", lexicon.collisions.len());
+                        for collision in &lexicon.collisions {
+                            let parent_form = collision.parent_id.and_then(|id| lexicon.graph.get(&id)).map_or("?", |p| p.form.as_str());
+                            let outcome = match &collision.resolution {
+                                CollisionResolution::HomophoneAdded => "kept as a homophone".to_string(),
+                                CollisionResolution::Rejected => "rejected".to_string(),
+                                CollisionResolution::Allomorph(affix) => format!("used allomorph '{}'", affix),
+                            };
+                            println!("'{}' from '{}' via '{}': {}", collision.form, parent_form, collision.rule_applied, outcome);
+                        }
+                    }
+
+                    let uncovered = generator.uncovered_concepts(&lexicon);
+                    println!("\n--- Concept Coverage ({} of {} concepts have a root) ---", generator.concept_count() - uncovered.len(), generator.concept_count());
+                    for (pos, meaning) in uncovered {
+                        println!("[UNCOVERED] {} ({})", meaning.gloss, pos);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
                 }
-                Err(e) => { /* ... error handling ... */ }
             }
         }
         Commands::Validate { lang } => {
@@ -99,9 +600,17 @@ fn main() {
             
 
             match initialize_from_config(lang) {
-                Ok(_) => {
-                    // If the function returns Ok, it means the file was read and parsed successfully.
-                    println!("\n✅ Success: Configuration file is valid and well-formed.");
+                Ok((inventory, generator)) => {
+                    // The file was read and parsed; now cross-check the rules against each other.
+                    let issues = generator.validate(&inventory);
+                    if issues.is_empty() {
+                        println!("\n✅ Success: Configuration file is valid and well-formed.");
+                    } else {
+                        eprintln!("\n❌ Error: Configuration file parsed, but has {} problem(s):", issues.len());
+                        for issue in issues {
+                            eprintln!("  - {}", issue);
+                        }
+                    }
                 }
                 Err(e) => {
                     // If it returns an Err, we print a specific, helpful error message.
@@ -111,14 +620,16 @@ fn main() {
             }
         }
 
-        Commands::Visualize { lang, count, passes, output } => {
+        Commands::Visualize { lang, count, passes, output, scheme } => {
             println!("--- Genesis Engine: Visualizer ---");
             println!("Loading language from: {}", lang);
 
             match initialize_from_config(lang) {
                 Ok((inventory, generator)) => {
+                    let Some(scheme) = resolve_scheme(&inventory, scheme) else { return };
                     println!("Generating lexicon with {} roots and {} derivation passes...", count, passes);
-                    let lexicon = generator.build_etymological_graph(*count, &inventory, *passes);
+                    let lexicon = generator.build_etymological_graph(*count, &inventory, *passes)
+                        .respelled(&inventory, &scheme);
                     
                     println!("Exporting graph to DOT format...");
                     let dot_output = genesis_engine_lexicon::export_to_dot(&lexicon);
@@ -145,7 +656,7 @@ fn main() {
     
 
 
-        Commands::Narrate { lang, roots, passes, num } => {
+        Commands::Narrate { lang, roots, passes, num, register } => {
                 println!("--- Genesis Engine: Narrator ---");
                 println!("Loading language from: {}", lang);
 
@@ -160,9 +671,17 @@ fn main() {
                         if lexicon.graph.is_empty() {
                             println!("Lexicon is empty, cannot generate sentences.");
                         } else {
+                            let register = register.map(|r| match r {
+                                RegisterArg::Formal => Register::Formal,
+                                RegisterArg::Common => Register::Common,
+                                RegisterArg::Slang => Register::Slang,
+                            });
                             for i in 0..*num {
-                                let sentence = generator.generate_sentence(&lexicon);
-                                println!("{}. {}", i + 1, sentence);
+                                let (register, sentence) = match register {
+                                    Some(r) => (r, generator.generate_sentence_in(&inventory, &lexicon, r)),
+                                    None => generator.generate_sentence(&inventory, &lexicon),
+                                };
+                                println!("{}. [{}] {}", i + 1, register.label(), sentence);
                             }
                         }
                     }
@@ -172,5 +691,607 @@ fn main() {
                     }
             }
         }
+
+        Commands::Lookup { lang, roots, passes, form, prefix, regex, meaning, pos, rule, min_depth, max_depth, sort, scheme, lexicon } => {
+            println!("--- Genesis Engine: Lexicon Lookup ---");
+            println!("Loading language from: {}", lang);
+
+            let form_match = if let Some(form) = form {
+                Some(FormMatch::Exact(form.clone()))
+            } else if let Some(prefix) = prefix {
+                Some(FormMatch::Prefix(prefix.clone()))
+            } else if let Some(pattern) = regex {
+                match Regex::new(pattern) {
+                    Ok(re) => Some(FormMatch::Regex(re)),
+                    Err(e) => {
+                        eprintln!("\n❌ Error: Invalid regular expression.");
+                        eprintln!("Reason: {}", e);
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+
+            match initialize_from_config(lang) {
+                Ok((inventory, generator)) => {
+                    // Forms are searched in the chosen spelling, so --form and friends use it too.
+                    let Some(scheme) = resolve_scheme(&inventory, scheme) else { return };
+                    let Some(native) = load_or_build(&generator, &inventory, lexicon.as_ref(), *roots, *passes) else { return };
+                    let lexicon = native.respelled(&inventory, &scheme);
+
+                    let query = LexiconQuery {
+                        form: form_match,
+                        meaning_contains: meaning.clone(),
+                        part_of_speech: pos.clone(),
+                        rule_applied: rule.clone(),
+                        min_depth: *min_depth,
+                        max_depth: *max_depth,
+                        sort: match sort {
+                            SortArg::Form => SortOrder::Form,
+                            SortArg::Gloss => SortOrder::Gloss,
+                        },
+                    };
+                    let results = lexicon.search(&query);
+
+                    println!("\n--- {} of {} words matched ---", results.len(), lexicon.graph.len());
+                    for lexeme in results {
+                        let guide = generator.pronunciation_guide(&inventory, &native.graph[&lexeme.id].form);
+                        match query.sort {
+                            SortOrder::Form => println!("{} [{}] ({}): {}", lexeme.form, guide, lexeme.part_of_speech, lexeme.gloss_list()),
+                            SortOrder::Gloss => println!("{} ({}): {} [{}]", lexeme.gloss_list(), lexeme.part_of_speech, lexeme.form, guide),
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
+                }
+            }
+        }
+
+        Commands::Etymology { lang, roots, passes, word, lexicon } => {
+            println!("--- Genesis Engine: Etymology ---");
+            println!("Loading language from: {}", lang);
+
+            match initialize_from_config(lang) {
+                Ok((inventory, generator)) => {
+                    let Some(lexicon) = load_or_build(&generator, &inventory, lexicon.as_ref(), *roots, *passes) else { return };
+
+                    let targets = match word {
+                        Some(word) => lexicon.find_by_form(word),
+                        // Every derived word, plus any root whose meaning has drifted.
+                        None => {
+                            let mut targets = lexicon.search(&LexiconQuery::default());
+                            targets.retain(|l| l.parent_id.is_some() || !l.meaning_shifts.is_empty());
+                            targets
+                        }
+                    };
+                    if targets.is_empty() {
+                        println!("\nNo matching words found in this lexicon.");
+                    }
+
+                    for lexeme in targets {
+                        println!("\n{} ({})", lexeme.form, lexeme.part_of_speech);
+                        for (i, sense) in lexeme.senses.iter().enumerate() {
+                            match &sense.source {
+                                SenseSource::Metaphor(name) => println!("  {}. {} (metaphor: {})", i + 1, sense.gloss, name),
+                                SenseSource::Drift => println!("  {}. {} (drift)", i + 1, sense.gloss),
+                                SenseSource::Core | SenseSource::Derived => println!("  {}. {}", i + 1, sense.gloss),
+                            }
+                        }
+                        if let Some(history) = generator.format_etymology(&lexicon, &lexeme.id) {
+                            println!("  History:     {}", history);
+                        }
+                        if let Some(meaning_history) = lexeme.meaning_history() {
+                            println!("  Meaning:     {}", meaning_history);
+                        }
+                        let variants: Vec<String> = generator.register_variants(&inventory, lexeme).into_iter()
+                            .filter(|v| !v.processes.is_empty())
+                            .map(|v| format!("{} {} ({})", v.register.label(), v.form, v.processes.join(", ")))
+                            .collect();
+                        if !variants.is_empty() {
+                            println!("  Registers:   {}", variants.join("; "));
+                        }
+                        if let Some(root) = lexicon.root_of(&lexeme.id) {
+                            println!("  Root:        {} '{}'", root.form, root.meaning());
+                        }
+                        let descendants = lexicon.descendants(&lexeme.id);
+                        if !descendants.is_empty() {
+                            let forms: Vec<&str> = descendants.iter().map(|d| d.form.as_str()).collect();
+                            println!("  Descendants: {}", forms.join(", "));
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
+                }
+            }
+        }
+
+        Commands::Name { lang, roots, passes, kind, template, num, lexicon } => {
+            println!("--- Genesis Engine: Naming Engine ---");
+            println!("Loading language from: {}", lang);
+
+            match initialize_from_config(lang) {
+                Ok((inventory, generator)) => {
+                    let Some(lexicon) = load_or_build(&generator, &inventory, lexicon.as_ref(), *roots, *passes) else { return };
+
+                    let kind = kind.map(|k| match k {
+                        KindArg::Person => NameKind::Person,
+                        KindArg::Place => NameKind::Place,
+                        KindArg::Polity => NameKind::Polity,
+                        KindArg::Clan => NameKind::Clan,
+                    });
+                    let templates: Vec<_> = generator.name_templates(&inventory).into_iter()
+                        .filter(|t| kind.is_none_or(|k| t.kind == k))
+                        .filter(|t| template.as_ref().is_none_or(|name| &t.name == name))
+                        .collect();
+                    if templates.is_empty() {
+                        eprintln!("\n❌ Error: No name template matches the requested kind or name.");
+                        return;
+                    }
+
+                    println!("\n--- Names ---");
+                    for i in 0..*num {
+                        let template = &templates[i % templates.len()];
+                        match generator.generate_name(&lexicon, &inventory, template) {
+                            Some(name) => {
+                                println!("{}. {} — '{}' [{}]", i + 1, name.name, name.gloss, name.template);
+                                for line in &name.etymology {
+                                    println!("     {}", line);
+                                }
+                            }
+                            None => println!("{}. (the lexicon has no words that fit the '{}' template)", i + 1, template.name),
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
+                }
+            }
+        }
+
+        Commands::Borrow { lang, donor, donor_name, domains, roots, passes } => {
+            println!("--- Genesis Engine: Borrowing ---");
+            println!("Loading recipient language from: {}", lang);
+            println!("Loading donor language from: {}", donor);
+
+            let donor_name = donor_name.clone().unwrap_or_else(|| {
+                Path::new(donor).file_stem().map_or(donor.clone(), |s| s.to_string_lossy().into_owned())
+            });
+
+            match (initialize_from_config(lang), initialize_from_config(donor)) {
+                (Ok((inventory, generator)), Ok((donor_inventory, donor_generator))) => {
+                    println!("\n--- Building {} ---", donor_name);
+                    let donor_lexicon = donor_generator.build_etymological_graph(*roots, &donor_inventory, *passes);
+                    println!("\n--- Building recipient ---");
+                    let mut lexicon = generator.build_etymological_graph(*roots, &inventory, *passes);
+
+                    println!("\n--- Borrowing from {} ---", donor_name);
+                    let borrowing = generator.borrow_words(&inventory, &mut lexicon, &donor_lexicon, &donor_name, domains);
+                    for id in &borrowing.unadapted {
+                        let source = &donor_lexicon.graph[id];
+                        println!("Could not adapt '{}' ({}); skipped.", source.form, source.meaning());
+                    }
+                    for collision in lexicon.collisions.iter().filter(|c| c.parent_id.is_none()) {
+                        let outcome = match collision.resolution {
+                            CollisionResolution::Rejected => "rejected",
+                            _ => "kept as a homophone",
+                        };
+                        println!("Loan '{}' sounds like an existing word: {}", collision.form, outcome);
+                    }
+
+                    println!("\n--- {} Loanwords ---", borrowing.loans.len());
+                    for id in &borrowing.loans {
+                        let lexeme = &lexicon.graph[id];
+                        let history = generator.format_etymology(&lexicon, id).unwrap_or_else(|| lexeme.form.clone());
+                        println!("{} ({}): {}", lexeme.form, lexeme.part_of_speech, lexeme.gloss_list());
+                        println!("  History:     {}", history);
+                    }
+                }
+                (Err(e), _) | (_, Err(e)) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
+                }
+            }
+        }
+
+        Commands::Script { lang, kind, seed, output } => {
+            println!("--- Genesis Engine: Script Generator ---");
+            println!("Loading language from: {}", lang);
+
+            match initialize_from_config(lang) {
+                Ok((inventory, mut generator)) => {
+                    if let Some(kind) = kind {
+                        generator.script.kind = match kind {
+                            ScriptArg::Alphabet => ScriptKind::Alphabet,
+                            ScriptArg::Abjad => ScriptKind::Abjad,
+                            ScriptArg::Abugida => ScriptKind::Abugida,
+                            ScriptArg::Syllabary => ScriptKind::Syllabary,
+                        };
+                    }
+                    if let Some(seed) = seed {
+                        generator.script.seed = *seed;
+                    }
+                    let script = match generator.generate_script(&inventory) {
+                        Ok(script) => script,
+                        Err(e) => {
+                            eprintln!("\nError: Failed to generate the script.");
+                            eprintln!("Reason: {}", e);
+                            return;
+                        }
+                    };
+                    println!("\n--- {} ({} signs, seed {}) ---", script.kind.label(), script.signs.len(), script.seed);
+                    print!("{}", script.mapping_table());
+
+                    let dir = Path::new(output);
+                    if let Err(e) = std::fs::create_dir_all(dir) {
+                        eprintln!("\n❌ Error: Could not create directory '{}': {}", output, e);
+                        return;
+                    }
+                    let mut files: Vec<(String, String)> = script.signs.iter().enumerate()
+                        .map(|(i, sign)| (sign.file_name(i), sign.glyph.to_svg()))
+                        .collect();
+                    files.push(("chart.svg".to_string(), script.sign_chart_svg()));
+                    files.push(("mapping.csv".to_string(), script.mapping_table()));
+                    for (name, contents) in files {
+                        if let Err(e) = std::fs::write(dir.join(&name), contents) {
+                            eprintln!("❌ Error: Could not write '{}': {}", dir.join(&name).display(), e);
+                            return;
+                        }
+                    }
+                    println!("\nWrote {} glyphs, chart.svg and mapping.csv to {}", script.signs.len(), dir.display());
+                }
+                Err(e) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
+                }
+            }
+        }
+
+        Commands::Write { lang, text, roots, passes, num, output } => {
+            println!("--- Genesis Engine: Scribe ---");
+            println!("Loading language from: {}", lang);
+
+            match initialize_from_config(lang) {
+                Ok((inventory, generator)) => {
+                    let script = match generator.generate_script(&inventory) {
+                        Ok(script) => script,
+                        Err(e) => {
+                            eprintln!("\nError: Failed to generate the script.");
+                            eprintln!("Reason: {}", e);
+                            return;
+                        }
+                    };
+                    let lines: Vec<String> = match text {
+                        Some(text) => vec![text.clone()],
+                        None => {
+                            let lexicon = generator.build_etymological_graph(*roots, &inventory, *passes);
+                            (0..*num).map(|_| generator.generate_sentence(&inventory, &lexicon).1).collect()
+                        }
+                    };
+
+                    println!("\n--- Written in the {} ---", script.kind.label());
+                    for line in &lines {
+                        println!("{}", line);
+                        println!("  {}", script.describe(&script.transliterate(&inventory, &line.to_lowercase())));
+                    }
+
+                    let text = lines.iter().map(|l| l.to_lowercase()).collect::<Vec<_>>().join(" ");
+                    match std::fs::write(output, script.render_svg(&inventory, &text)) {
+                        Ok(()) => println!("\nRendered text saved to: {}", output),
+                        Err(e) => eprintln!("\n❌ Error: Could not write '{}': {}", output, e),
+                    }
+                }
+                Err(e) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
+                }
+            }
+        }
+
+        Commands::Font { lang, mapping, family, roots, num, output } => {
+            println!("--- Genesis Engine: Font Foundry ---");
+            println!("Loading language from: {}", lang);
+
+            match initialize_from_config(lang) {
+                Ok((inventory, generator)) => {
+                    let mapping = match mapping {
+                        MappingArg::PrivateUse => FontMapping::PrivateUse,
+                        MappingArg::Latin => FontMapping::Latin,
+                    };
+                    let script = match generator.generate_script(&inventory) {
+                        Ok(script) => script,
+                        Err(e) => {
+                            eprintln!("\nError: Failed to generate the script.");
+                            eprintln!("Reason: {}", e);
+                            return;
+                        }
+                    };
+                    let lexicon = generator.build_etymological_graph(*roots, &inventory, 1);
+                    let samples: Vec<String> = (0..*num).map(|_| generator.generate_sentence(&inventory, &lexicon).1).collect();
+
+                    let dir = Path::new(output);
+                    if let Err(e) = std::fs::create_dir_all(dir) {
+                        eprintln!("\n❌ Error: Could not create directory '{}': {}", output, e);
+                        return;
+                    }
+                    let font_file = format!("{}.ttf", family.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>());
+                    let files = [
+                        (font_file.clone(), script.to_ttf(family, mapping)),
+                        ("specimen.html".to_string(), script.specimen_html(&inventory, family, &font_file, mapping, &samples).into_bytes()),
+                        ("codepoints.csv".to_string(), script.font_mapping_table(mapping).into_bytes()),
+                    ];
+                    for (name, contents) in files {
+                        match std::fs::write(dir.join(&name), contents) {
+                            Ok(()) => println!("Wrote {}", dir.join(&name).display()),
+                            Err(e) => {
+                                eprintln!("❌ Error: Could not write '{}': {}", dir.join(&name).display(), e);
+                                return;
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
+                }
+            }
+        }
+
+        Commands::Dialects { lang, roots, passes, steps, words, output, map } => {
+            println!("--- Genesis Engine: Dialect Simulator ---");
+            println!("Loading language from: {}", lang);
+
+            match initialize_from_config(lang) {
+                Ok((inventory, mut generator)) => {
+                    if let Some(steps) = steps {
+                        generator.dialects.steps = *steps;
+                    }
+                    if let Some(map) = map {
+                        generator.dialects.map = Some(map.clone());
+                    }
+                    let graph = match generator.dialects.community_graph() {
+                        Ok(graph) => graph,
+                        Err(e) => {
+                            eprintln!("\nError: Failed to build the community graph.");
+                            eprintln!("Reason: {}", e);
+                            return;
+                        }
+                    };
+                    let proto = generator.build_etymological_graph(*roots, &inventory, *passes);
+
+                    println!("\n--- Dialect Formation ({} communities) ---", graph.communities.len());
+                    let simulation = generator.simulate_dialects(&proto, &inventory, &graph);
+                    for innovation in &simulation.innovations {
+                        println!("Step {}: {} arose in {} and reached {} of {} communities",
+                            innovation.step, innovation.kind.describe(), graph.communities[innovation.origin].name,
+                            innovation.adopters.len(), graph.communities.len());
+                    }
+
+                    println!("\n--- Comparative Word List ---");
+                    let names: Vec<&str> = graph.communities.iter().map(|c| c.name.as_str()).collect();
+                    let mut compared: Vec<_> = proto.roots.iter().filter_map(|id| proto.graph.get(id)).collect();
+                    compared.sort_by(|a, b| a.meaning().cmp(b.meaning()));
+                    compared.truncate(*words);
+                    let rows: Vec<Vec<&str>> = compared.iter()
+                        .map(|root| simulation.lexicons.iter()
+                            .map(|l| l.graph.get(&root.id).map_or("-", |w| w.form.as_str()))
+                            .collect())
+                        .collect();
+                    let meaning_width = compared.iter().map(|r| r.meaning().chars().count()).max().unwrap_or(0).max(7);
+                    let widths: Vec<usize> = names.iter().enumerate()
+                        .map(|(c, name)| rows.iter().map(|r| r[c].chars().count()).chain([name.chars().count()]).max().unwrap_or(0))
+                        .collect();
+                    let line = |first: &str, cells: &[&str]| {
+                        let cells: Vec<String> = cells.iter().zip(&widths).map(|(cell, w)| format!("{:<w$}", cell, w = w)).collect();
+                        format!("{:<mw$} | {}", first, cells.join(" | "), mw = meaning_width)
+                    };
+                    println!("{}", line("meaning", &names));
+                    for (root, row) in compared.iter().zip(&rows) {
+                        println!("{}", line(root.meaning(), row));
+                    }
+
+                    println!("\n--- Isoglosses ---");
+                    let isoglosses = simulation.isoglosses();
+                    if isoglosses.is_empty() {
+                        println!("No innovation stopped short of any community; the language is still uniform.");
+                    }
+                    for isogloss in &isoglosses {
+                        let innovation = &simulation.innovations[isogloss.innovation];
+                        let adopters: Vec<&str> = innovation.adopters.iter().map(|&c| names[c]).collect();
+                        let boundary: Vec<String> = isogloss.boundary.iter()
+                            .map(|&(a, b)| format!("{}|{}", names[a], names[b]))
+                            .collect();
+                        println!("{} (step {})", innovation.kind.describe(), innovation.step);
+                        println!("  Spoken in: {}", adopters.join(", "));
+                        println!("  Boundary:  {}", boundary.join(", "));
+                    }
+
+                    println!("\n--- Divergence Between Neighbours ---");
+                    for link in &graph.links {
+                        println!("{} - {}: {} differing innovations", names[link.a], names[link.b], simulation.divergence(link.a, link.b));
+                    }
+
+                    if let Some(dir) = output {
+                        if let Err(e) = std::fs::create_dir_all(dir) {
+                            eprintln!("\n❌ Error: Could not create directory '{}': {}", dir, e);
+                            return;
+                        }
+                        for (community, lexicon) in graph.communities.iter().zip(&simulation.lexicons) {
+                            let file_name: String = community.name.chars()
+                                .map(|c| if c.is_alphanumeric() { c } else { '_' })
+                                .collect();
+                            let path = Path::new(dir).join(format!("{}.dot", file_name));
+                            match std::fs::write(&path, genesis_engine_lexicon::export_to_dot(lexicon)) {
+                                Ok(()) => println!("Wrote {}", path.display()),
+                                Err(e) => eprintln!("❌ Error: Could not write '{}': {}", path.display(), e),
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
+                }
+            }
+        }
+
+        Commands::Pronounce { lang, words, roots, passes, output } => {
+            println!("--- Genesis Engine: Pronunciation Guide ---");
+            println!("Loading language from: {}", lang);
+
+            match initialize_from_config(lang) {
+                Ok((inventory, generator)) => {
+                    println!("\n--- Pronunciation Key ---");
+                    println!("{}", generator.pronunciation.stress.describe());
+                    for entry in generator.pronunciation_key(&inventory) {
+                        println!("{:<4} /{}/  say \"{}\", as in {}", entry.grapheme, entry.ipa, entry.respelling, entry.as_in);
+                    }
+
+                    if !words.is_empty() {
+                        println!("\n--- Sounds Like ---");
+                        for word in words {
+                            println!("{}: {}", word, generator.pronunciation_guide(&inventory, &word.to_lowercase()));
+                        }
+                    }
+
+                    if let Some(output) = output {
+                        let lexicon = generator.build_etymological_graph(*roots, &inventory, *passes);
+                        match std::fs::write(output, generator.export_dictionary(&inventory, &lexicon)) {
+                            Ok(()) => println!("\n✅ Success: Dictionary of {} words saved to '{}'", lexicon.graph.len(), output),
+                            Err(e) => eprintln!("\n❌ Error: Could not write '{}': {}", output, e),
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
+                }
+            }
+        }
+
+        Commands::Speak { lang, text, roots, passes, words, num, output } => {
+            println!("--- Genesis Engine: Voice ---");
+            println!("Loading language from: {}", lang);
+
+            match initialize_from_config(lang) {
+                Ok((inventory, generator)) => {
+                    // (file name, text) for every clip to record.
+                    let clips: Vec<(String, String)> = match text {
+                        Some(text) => vec![("speech".to_string(), text.to_lowercase())],
+                        None => {
+                            let lexicon = generator.build_etymological_graph(*roots, &inventory, *passes);
+                            let word_clips = lexicon.roots.iter().take(*words).map(|id| (lexicon.graph[id].form.clone(), lexicon.graph[id].form.clone()));
+                            let sentence_clips = (0..*num).map(|i| (format!("sentence_{}", i + 1), generator.generate_sentence(&inventory, &lexicon).1.to_lowercase()));
+                            word_clips.chain(sentence_clips).collect()
+                        }
+                    };
+
+                    if let Err(e) = std::fs::create_dir_all(output) {
+                        eprintln!("\n❌ Error: Could not create directory '{}': {}", output, e);
+                        return;
+                    }
+                    println!();
+                    for (name, text) in clips {
+                        let speech = generator.synthesize(&inventory, &text);
+                        let file_name: String = name.chars().map(|c| if c.is_alphanumeric() { c } else { '_' }).collect();
+                        let path = Path::new(output).join(format!("{}.wav", file_name));
+                        match std::fs::write(&path, speech.to_wav()) {
+                            Ok(()) => println!("{} [{}] -> {} ({:.1}s)", text, generator.pronunciation_guide(&inventory, &text), path.display(), speech.duration()),
+                            Err(e) => eprintln!("❌ Error: Could not write '{}': {}", path.display(), e),
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
+                }
+            }
+        }
+
+        Commands::Text { lang, kind, template, history, events, roots, passes, output } => {
+            println!("--- Genesis Engine: Historical Texts ---");
+            println!("Loading language from: {}", lang);
+
+            match initialize_from_config(lang) {
+                Ok((inventory, generator)) => {
+                    let kind = match kind {
+                        TextArg::Decree => TextKind::Decree,
+                        TextArg::Inscription => TextKind::Inscription,
+                        TextArg::Chronicle => TextKind::Chronicle,
+                    };
+                    let Some(text_template) = generator.text_templates().into_iter()
+                        .find(|t| t.kind == kind && template.as_ref().is_none_or(|name| &t.name == name)) else {
+                        eprintln!("\n❌ Error: No text template matches the requested kind or name.");
+                        return;
+                    };
+                    let history = match history.as_ref().or(generator.texts.history.as_ref()) {
+                        Some(path) => match historical_text::load_history(path) {
+                            Ok(events) => {
+                                println!("Read {} events from: {}", events.len(), path);
+                                events
+                            }
+                            Err(e) => {
+                                eprintln!("\n❌ Error: Could not read the history log '{}'.", path);
+                                eprintln!("Reason: {}", e);
+                                return;
+                            }
+                        },
+                        None => Vec::new(),
+                    };
+
+                    let lexicon = generator.build_etymological_graph(*roots, &inventory, *passes);
+                    let text = generator.generate_text(&inventory, &lexicon, &text_template, &history, *events);
+
+                    println!("\n--- {} ---", text.template);
+                    println!("{}", text.text());
+                    println!("\n--- Interlinear Translation ---");
+                    for sentence in &text.sentences {
+                        println!("{}\n", sentence.interlinear());
+                    }
+
+                    if let Some(output) = output {
+                        match std::fs::write(output, text.render()) {
+                            Ok(()) => println!("✅ Success: Text saved to '{}'", output),
+                            Err(e) => eprintln!("❌ Error: Could not write '{}': {}", output, e),
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
+                }
+            }
+        }
+
+        Commands::Convert { lang, from, to, text } => {
+            match initialize_from_config(lang) {
+                Ok((inventory, _)) => {
+                    let (Some(from), Some(to)) = (resolve_scheme(&inventory, from), resolve_scheme(&inventory, to)) else { return };
+                    let Some(text) = text else {
+                        // No text: show how every scheme spells each phoneme instead.
+                        let schemes = inventory.schemes();
+                        println!("{}", schemes.iter().map(|s| s.name()).collect::<Vec<_>>().join("\t"));
+                        for phoneme in inventory.phonemes() {
+                            println!("{}", schemes.iter().map(|s| phoneme.spelling(s)).collect::<Vec<_>>().join("\t"));
+                        }
+                        return;
+                    };
+                    println!("{}", inventory.convert(text, &from, &to));
+                    let clashes = inventory.ambiguous_spellings(&from);
+                    if !clashes.is_empty() {
+                        eprintln!("Warning: '{}' spells {} pair(s) of phonemes alike, so the conversion may have guessed.", from.name(), clashes.len());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("\nError: Failed to initialize generator.");
+                    eprintln!("Reason: {}", e);
+                }
+            }
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,263 @@
+use crate::dialect::{Community, CommunityGraph, Link};
+use crate::ConfigError;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+// Extra travel cost of each kind of terrain, on top of distance (where the average link
+// is scaled to 1, like one grid step). Region terrain is averaged over a link's two ends;
+// a line feature such as a river is paid once per crossing.
+const TERRAIN_COSTS: &[(&str, f64)] = &[
+    ("plains", 0.0),
+    ("grassland", 0.0),
+    ("coast", 0.0),
+    ("farmland", 0.0),
+    ("forest", 0.5),
+    ("hills", 1.0),
+    ("desert", 1.5),
+    ("marsh", 1.5),
+    ("swamp", 1.5),
+    ("river", 2.0),
+    ("mountain", 4.0),
+    ("sea", 6.0),
+];
+
+// Built-in terrain costs with any configured overrides on top. Unknown terrain costs nothing.
+pub fn terrain_cost(terrain: &str, overrides: &HashMap<String, f64>) -> f64 {
+    overrides.get(terrain).copied()
+        .or_else(|| TERRAIN_COSTS.iter().find(|(name, _)| *name == terrain).map(|(_, cost)| *cost))
+        .unwrap_or(0.0)
+        .max(0.0)
+}
+
+type Point = (f64, f64);
+
+// One region read from a GeoJSON polygon.
+struct Region {
+    name: String,
+    terrain: String,
+    extra_cost: f64,
+    centroid: Point,
+    vertices: HashSet<(i64, i64)>,
+}
+
+impl CommunityGraph {
+    // Reads a map from disk: `.csv` files as adjacency lists, anything else as GeoJSON.
+    pub fn import(path: &str, terrain_costs: &HashMap<String, f64>) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(ConfigError::FileRead)?;
+        let is_csv = Path::new(path).extension().is_some_and(|e| e.eq_ignore_ascii_case("csv"));
+        if is_csv {
+            Self::from_csv(&contents, terrain_costs)
+        } else {
+            Self::from_geojson(&contents, terrain_costs)
+        }
+    }
+
+    // Builds communities from a GeoJSON FeatureCollection. Every Polygon or MultiPolygon is
+    // a community; two of them are neighbours when their borders share at least two vertices.
+    // LineString features (rivers, walls, ridges) add their terrain cost to every link they cross.
+    // Recognised properties: "name", "terrain" and "cost" (an extra cost for the region itself).
+    pub fn from_geojson(text: &str, terrain_costs: &HashMap<String, f64>) -> Result<Self, ConfigError> {
+        let root: Value = serde_json::from_str(text).map_err(ConfigError::JsonParse)?;
+        let features = root.get("features").and_then(Value::as_array)
+            .ok_or_else(|| ConfigError::MapImport("expected a GeoJSON FeatureCollection with a \"features\" array".to_string()))?;
+
+        let mut regions: Vec<Region> = Vec::new();
+        let mut lines: Vec<(Vec<Point>, f64)> = Vec::new();
+        for (i, feature) in features.iter().enumerate() {
+            let properties = feature.get("properties");
+            let property = |key: &str| properties.and_then(|p| p.get(key));
+            let terrain = property("terrain").and_then(Value::as_str).unwrap_or("plains").to_string();
+            let geometry = feature.get("geometry")
+                .ok_or_else(|| ConfigError::MapImport(format!("feature {} has no geometry", i)))?;
+            let coordinates = geometry.get("coordinates")
+                .ok_or_else(|| ConfigError::MapImport(format!("feature {} has no coordinates", i)))?;
+
+            match geometry.get("type").and_then(Value::as_str) {
+                Some("Polygon") | Some("MultiPolygon") => {
+                    let polygons = if geometry["type"] == "Polygon" {
+                        vec![rings(coordinates, i)?]
+                    } else {
+                        coordinates.as_array().into_iter().flatten()
+                            .map(|polygon| rings(polygon, i))
+                            .collect::<Result<Vec<_>, _>>()?
+                    };
+                    let outer: Vec<&Vec<Point>> = polygons.iter().filter_map(|p| p.first()).collect();
+                    let centroid = centroid(&outer)
+                        .ok_or_else(|| ConfigError::MapImport(format!("feature {} has an empty polygon", i)))?;
+                    regions.push(Region {
+                        name: property("name").and_then(Value::as_str).map_or_else(|| format!("region {}", i), str::to_string),
+                        extra_cost: property("cost").and_then(Value::as_f64).unwrap_or(0.0),
+                        terrain,
+                        centroid,
+                        vertices: polygons.iter().flatten().flatten().map(|&p| vertex_key(p)).collect(),
+                    });
+                }
+                Some("LineString") => lines.push((points(coordinates, i)?, terrain_cost(&terrain, terrain_costs))),
+                Some("MultiLineString") => {
+                    for line in coordinates.as_array().into_iter().flatten() {
+                        lines.push((points(line, i)?, terrain_cost(&terrain, terrain_costs)));
+                    }
+                }
+                _ => {} // Points and other geometry carry no regions or borders.
+            }
+        }
+
+        let mut pairs = Vec::new();
+        for a in 0..regions.len() {
+            for b in a + 1..regions.len() {
+                if regions[a].vertices.intersection(&regions[b].vertices).count() >= 2 {
+                    pairs.push((a, b));
+                }
+            }
+        }
+
+        let mut graph = CommunityGraph {
+            communities: regions.iter()
+                .map(|r| Community { name: r.name.clone(), x: r.centroid.0, y: r.centroid.1 })
+                .collect(),
+            links: Vec::new(),
+        };
+        let scale = mean(pairs.iter().map(|&(a, b)| graph.distance(a, b)));
+        for (a, b) in pairs {
+            let (from, to) = (regions[a].centroid, regions[b].centroid);
+            let terrain = (terrain_cost(&regions[a].terrain, terrain_costs) + regions[a].extra_cost
+                + terrain_cost(&regions[b].terrain, terrain_costs) + regions[b].extra_cost) / 2.0;
+            let crossings: f64 = lines.iter()
+                .map(|(line, cost)| line.windows(2).filter(|s| segments_cross(from, to, s[0], s[1])).count() as f64 * cost)
+                .sum();
+            let cost = graph.distance(a, b) / scale + terrain + crossings;
+            graph.links.push(Link { a, b, cost });
+        }
+        Ok(graph)
+    }
+
+    // Builds communities from an adjacency list. The first line is a header naming the
+    // columns: "from" and "to" are required, "distance" (default 1) and "terrain" (any
+    // number of terrain types separated by ';') are optional. Blank lines and lines
+    // starting with '#' are skipped. Communities are created as their names appear.
+    pub fn from_csv(text: &str, terrain_costs: &HashMap<String, f64>) -> Result<Self, ConfigError> {
+        let mut rows = text.lines().enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+        let (_, header) = rows.next().ok_or_else(|| ConfigError::MapImport("the CSV file is empty".to_string()))?;
+        let columns: Vec<String> = split_csv(header).iter().map(|c| c.to_lowercase()).collect();
+        let column = |name: &str| columns.iter().position(|c| c == name);
+        let (from_column, to_column) = match (column("from"), column("to")) {
+            (Some(from), Some(to)) => (from, to),
+            _ => return Err(ConfigError::MapImport("the CSV header needs \"from\" and \"to\" columns".to_string())),
+        };
+        let (distance_column, terrain_column) = (column("distance"), column("terrain"));
+
+        let mut graph = CommunityGraph::default();
+        let mut edges = Vec::new();
+        for (number, line) in rows {
+            let fields = split_csv(line);
+            let field = |index: Option<usize>| index.and_then(|i| fields.get(i)).map(|f| f.as_str()).filter(|f| !f.is_empty());
+            let (Some(from), Some(to)) = (field(Some(from_column)), field(Some(to_column))) else {
+                return Err(ConfigError::MapImport(format!("line {} is missing a community name", number + 1)));
+            };
+            let distance = match field(distance_column) {
+                Some(d) => d.parse::<f64>()
+                    .map_err(|_| ConfigError::MapImport(format!("line {}: '{}' is not a distance", number + 1, d)))?,
+                None => 1.0,
+            };
+            let terrain: f64 = field(terrain_column).unwrap_or("")
+                .split(';')
+                .map(|t| terrain_cost(t.trim(), terrain_costs))
+                .sum();
+
+            let mut index_of = |name: &str| match graph.index_of(name) {
+                Some(index) => index,
+                None => {
+                    // Positions are unknown; every community sits at the origin.
+                    graph.communities.push(Community { name: name.to_string(), x: 0.0, y: 0.0 });
+                    graph.communities.len() - 1
+                }
+            };
+            let (a, b) = (index_of(from), index_of(to));
+            edges.push((a, b, distance.max(0.0), terrain));
+        }
+
+        let scale = mean(edges.iter().map(|&(_, _, distance, _)| distance));
+        for (a, b, distance, terrain) in edges {
+            graph.links.push(Link { a, b, cost: distance / scale + terrain });
+        }
+        Ok(graph)
+    }
+}
+
+fn points(value: &Value, feature: usize) -> Result<Vec<Point>, ConfigError> {
+    value.as_array().into_iter().flatten()
+        .map(|p| match (p.get(0).and_then(Value::as_f64), p.get(1).and_then(Value::as_f64)) {
+            (Some(x), Some(y)) => Ok((x, y)),
+            _ => Err(ConfigError::MapImport(format!("feature {} has a malformed coordinate", feature))),
+        })
+        .collect()
+}
+
+// A polygon's rings; the first is the outer border, the rest are holes.
+fn rings(value: &Value, feature: usize) -> Result<Vec<Vec<Point>>, ConfigError> {
+    value.as_array().into_iter().flatten().map(|ring| points(ring, feature)).collect()
+}
+
+// Coordinates rounded so vertices shared by two exported polygons compare equal.
+fn vertex_key((x, y): Point) -> (i64, i64) {
+    ((x * 1e6).round() as i64, (y * 1e6).round() as i64)
+}
+
+// The area-weighted centroid of some rings, or the mean of their vertices if they enclose no area.
+fn centroid(rings: &[&Vec<Point>]) -> Option<Point> {
+    let (mut area, mut cx, mut cy) = (0.0, 0.0, 0.0);
+    for ring in rings {
+        for w in ring.windows(2) {
+            let cross = w[0].0 * w[1].1 - w[1].0 * w[0].1;
+            area += cross / 2.0;
+            cx += (w[0].0 + w[1].0) * cross / 6.0;
+            cy += (w[0].1 + w[1].1) * cross / 6.0;
+        }
+    }
+    if area.abs() > f64::EPSILON {
+        return Some((cx / area, cy / area));
+    }
+    let all: Vec<&Point> = rings.iter().flat_map(|r| r.iter()).collect();
+    if all.is_empty() {
+        return None;
+    }
+    let n = all.len() as f64;
+    Some((all.iter().map(|p| p.0).sum::<f64>() / n, all.iter().map(|p| p.1).sum::<f64>() / n))
+}
+
+// Whether segment a-b properly crosses segment c-d.
+fn segments_cross(a: Point, b: Point, c: Point, d: Point) -> bool {
+    let orientation = |p: Point, q: Point, r: Point| (q.0 - p.0) * (r.1 - p.1) - (q.1 - p.1) * (r.0 - p.0);
+    let (d1, d2) = (orientation(c, d, a), orientation(c, d, b));
+    let (d3, d4) = (orientation(a, b, c), orientation(a, b, d));
+    d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+// The mean of some positive distances, or 1.0 if there are none.
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0), |(s, c), v| (s + v, c + 1));
+    if count == 0 || sum <= 0.0 { 1.0 } else { sum / count as f64 }
+}
+
+// Splits one CSV line on commas, honouring double-quoted fields.
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
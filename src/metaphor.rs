@@ -0,0 +1,184 @@
+use crate::{Lexeme, Lexicon, Origin, Sense, SenseSource, WordGenerator};
+use rand::prelude::*;
+use serde::Deserialize;
+use uuid::Uuid;
+
+// A conceptual metaphor such as ARGUMENT IS WAR: words from the source domain (a semantic
+// tag, e.g. "violence") gain abstract senses in the target domain (e.g. "argument").
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConceptualMetaphor {
+    pub name: String,          // e.g. "ARGUMENT IS WAR"
+    pub source_domain: String,
+    pub target_domain: String,
+    #[serde(default)]
+    pub extensions: Vec<SenseExtension>, // Hand-picked senses; these always apply.
+    #[serde(default)]
+    pub sense_template: Option<String>,  // For other source words, e.g. "{meaning} (in argument)"
+    #[serde(default = "default_extension_chance")]
+    pub extension_chance: f64,           // Chance a source word without a hand-picked sense gains one.
+    #[serde(default)]
+    pub idioms: usize,                   // How many verb + noun idioms to coin from the source domain.
+    #[serde(default = "default_idiom_template")]
+    pub idiom_template: String,
+}
+
+// "The word meaning `source` also comes to mean `target`", e.g. cleave -> decide.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SenseExtension {
+    pub source: String,
+    pub target: String,
+}
+
+fn default_extension_chance() -> f64 { 0.3 }
+fn default_idiom_template() -> String { "to {verb} the {noun} (figuratively, of {target})".to_string() }
+
+impl WordGenerator {
+    // Runs every configured metaphor over the lexicon. An extended meaning becomes an extra
+    // sense of the word it grew from; an idiom becomes a child lexeme of its head verb, with
+    // the verb and its object in the grammar's word order. Each idiom pairs a different verb
+    // and noun, and one that sounds like an existing word follows the homophony policy.
+    pub fn apply_metaphors(&self, lexicon: &mut Lexicon) {
+        let mut rng = rand::rng();
+        let mut form_to_ids = lexicon.form_index();
+
+        for metaphor in &self.lexicon_generation.metaphors {
+            // Only words that existed before this metaphor ran take part.
+            let mut source_ids: Vec<Uuid> = lexicon.graph.values()
+                .filter(|l| l.origin != Origin::Idiom && l.tags().contains(&metaphor.source_domain))
+                .map(|l| l.id)
+                .collect();
+            source_ids.sort();
+
+            for id in &source_ids {
+                let Some(source) = lexicon.graph.get_mut(id) else { continue };
+                let target_meaning = match metaphor.extensions.iter().find(|e| e.source == source.meaning()) {
+                    Some(extension) => extension.target.clone(),
+                    None => match &metaphor.sense_template {
+                        Some(template) if rng.random_bool(metaphor.extension_chance.clamp(0.0, 1.0)) => {
+                            template.replace("{meaning}", source.meaning())
+                        }
+                        _ => continue,
+                    },
+                };
+                if source.has_sense(&target_meaning) {
+                    continue;
+                }
+                let sense = Sense::new(target_meaning, metaphorical_tags(source.tags(), metaphor), SenseSource::Metaphor(metaphor.name.clone()));
+                source.senses.push(sense);
+            }
+
+            // Idioms are built from plain roots, which keeps them short enough to say.
+            let roots: Vec<&Lexeme> = source_ids.iter()
+                .filter_map(|id| lexicon.graph.get(id))
+                .filter(|l| l.origin == Origin::Root)
+                .collect();
+            let verbs = roots.iter().filter(|l| l.part_of_speech == "verb");
+            let nouns: Vec<&Lexeme> = roots.iter().copied().filter(|l| l.part_of_speech == "noun").collect();
+            let mut pairs: Vec<(&Lexeme, &Lexeme)> = verbs
+                .flat_map(|verb| nouns.iter().map(move |noun| (*verb, *noun)))
+                .collect();
+            pairs.shuffle(&mut rng);
+            let mut idioms = Vec::new();
+            for (verb, noun) in pairs.into_iter().take(metaphor.idioms) {
+                let meaning = metaphor.idiom_template
+                    .replace("{verb}", verb.meaning())
+                    .replace("{noun}", noun.meaning())
+                    .replace("{target}", &metaphor.target_domain);
+                let form = self.grammar.arrange(None, Some(verb.form.as_str()), Some(noun.form.as_str()))
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                idioms.push(Lexeme {
+                    id: Uuid::new_v4(),
+                    form,
+                    part_of_speech: "idiom".to_string(),
+                    senses: vec![Sense::new(meaning, metaphorical_tags(verb.tags(), metaphor), SenseSource::Metaphor(metaphor.name.clone()))],
+                    meaning_shifts: Vec::new(),
+                    form_changes: Vec::new(),
+                    parent_id: Some(verb.id),
+                    rule_applied: Some(metaphor.name.clone()),
+                    affix_applied: None,
+                    origin: Origin::Idiom,
+                    borrowed_from: None,
+                });
+            }
+
+            for idiom in idioms {
+                if !self.admits_form(lexicon, &form_to_ids, &idiom.form, idiom.parent_id, metaphor.name.clone()) {
+                    continue;
+                }
+                form_to_ids.entry(idiom.form.clone()).or_default().push(idiom.id);
+                lexicon.add_lexeme(idiom);
+            }
+        }
+    }
+}
+
+// The source sense's tags, moved out of the source domain and into the (abstract) target domain.
+fn metaphorical_tags(source_tags: &[String], metaphor: &ConceptualMetaphor) -> Vec<String> {
+    let mut tags: Vec<String> = source_tags.iter()
+        .filter(|t| **t != metaphor.source_domain && t.as_str() != "concrete")
+        .cloned()
+        .collect();
+    for tag in ["abstract", metaphor.target_domain.as_str()] {
+        if !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{generator, root};
+    use crate::CollisionResolution;
+    use serde_json::json;
+
+    fn war_generator(idioms: usize) -> WordGenerator {
+        generator(json!({
+            "grammar": { "word_order": "SOV" },
+            "lexicon_generation": { "metaphors": [{
+                "name": "ARGUMENT IS WAR",
+                "source_domain": "violence",
+                "target_domain": "argument",
+                "idioms": idioms,
+            }] },
+        }))
+    }
+
+    fn idioms(lexicon: &Lexicon) -> Vec<&Lexeme> {
+        lexicon.graph.values().filter(|l| l.origin == Origin::Idiom).collect()
+    }
+
+    #[test]
+    fn idioms_pair_distinct_words_in_the_grammars_order() {
+        let mut lexicon = Lexicon::new();
+        for (form, pos, gloss) in [("tuk", "verb", "fight"), ("rad", "verb", "avenge"), ("gor", "noun", "axe")] {
+            lexicon.add_lexeme(root(form, pos, gloss, &["violence"]));
+        }
+        // Only two verb + noun pairs exist, so asking for five coins two.
+        war_generator(5).apply_metaphors(&mut lexicon);
+
+        let mut forms: Vec<&str> = idioms(&lexicon).iter().map(|l| l.form.as_str()).collect();
+        forms.sort();
+        assert_eq!(forms, ["gor rad", "gor tuk"]);
+        assert!(!lexicon.roots.iter().any(|id| lexicon.graph[id].origin == Origin::Idiom));
+    }
+
+    #[test]
+    fn an_idiom_that_sounds_like_a_word_follows_the_homophony_policy() {
+        let mut lexicon = Lexicon::new();
+        for (form, pos, gloss) in [("tuk", "verb", "fight"), ("gor", "noun", "axe")] {
+            lexicon.add_lexeme(root(form, pos, gloss, &["violence"]));
+        }
+        lexicon.add_lexeme(root("gor tuk", "noun", "battle-axe", &[]));
+        war_generator(1).apply_metaphors(&mut lexicon);
+
+        assert!(idioms(&lexicon).is_empty());
+        assert_eq!(lexicon.collisions.len(), 1);
+        assert_eq!(lexicon.collisions[0].form, "gor tuk");
+        assert_eq!(lexicon.collisions[0].resolution, CollisionResolution::Rejected);
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{Lexeme, Lexicon, Origin, PhoneticInventory, WordGenerator};
+use rand::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct Naming {
+    #[serde(default)]
+    pub templates: Vec<NameTemplate>, // Falls back to `default_name_templates` when empty.
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum NameKind {
+    Person,
+    Place,
+    Polity,
+    Clan,
+}
+
+// A recipe for one style of name, e.g. a toponym or a patronymic.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NameTemplate {
+    pub name: String, // e.g. "Toponym", "Patronymic (male)"
+    pub kind: NameKind,
+    pub parts: Vec<NamePart>,
+    #[serde(default)]
+    pub separator: String, // Between parts; "" makes one word, " " makes several.
+    #[serde(default)]
+    pub gloss_template: Option<String>, // e.g. "{0}, son of {2}"; glosses are joined with spaces if absent.
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum NamePart {
+    // A word from the lexicon with this part of speech and all of these tags, optionally
+    // put through a derivational rule (e.g. "LocationOf" for a toponym).
+    Word {
+        #[serde(default)]
+        pos: Option<String>,
+        #[serde(default)]
+        tags: Vec<String>,
+        #[serde(default)]
+        rule: Option<String>,
+        #[serde(default)]
+        max_depth: Option<usize>, // 0 = roots only, which keeps names short.
+    },
+    // A fixed piece such as a gender/class marker or a patronymic particle. A leading or
+    // trailing hyphen ("-in", "bar-") attaches it to its neighbour like an affix.
+    Marker { form: String, gloss: String },
+    // A whole name of this kind, e.g. the father's name in a patronymic, glossed as the name
+    // itself. It is built from the named template, or else any template of that kind, that
+    // has no `Name` parts of its own.
+    Name {
+        kind: NameKind,
+        #[serde(default)]
+        template: Option<String>,
+    },
+}
+
+// A finished name with its literal meaning and where each piece came from.
+#[derive(Debug, Clone)]
+pub struct GeneratedName {
+    pub name: String,
+    pub kind: NameKind,
+    pub template: String,
+    pub gloss: String,
+    pub etymology: Vec<String>, // One line per word used.
+}
+
+// Templates for a language that configures none. Given names end in a gender marker made
+// from the inventory's vowels: the last vowel for male names, the first for female ones (plus
+// the first consonant when there is only one vowel, so the two still differ).
+pub fn default_name_templates(inventory: &PhoneticInventory) -> Vec<NameTemplate> {
+    let first_vowel = inventory.vowels.first().map_or("", |v| v.grapheme());
+    let last_vowel = inventory.vowels.last().map_or("", |v| v.grapheme());
+    let male = format!("-{}", last_vowel);
+    let female = if inventory.vowels.len() > 1 {
+        format!("-{}", first_vowel)
+    } else {
+        format!("-{}{}", first_vowel, inventory.consonants.first().map_or("", |c| c.grapheme()))
+    };
+    let marker = |form: &str, gloss: &str| NamePart::Marker { form: form.to_string(), gloss: gloss.to_string() };
+    let word = |pos: &str, tags: &[&str]| NamePart::Word {
+        pos: Some(pos.to_string()),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        rule: None,
+        max_depth: Some(0),
+    };
+    let template = |name: &str, kind, parts, separator: &str, gloss: &str| NameTemplate {
+        name: name.to_string(),
+        kind,
+        parts,
+        separator: separator.to_string(),
+        gloss_template: Some(gloss.to_string()),
+    };
+    vec![
+        template("Given name (male)", NameKind::Person, vec![word("adj", &[]), word("noun", &[]), marker(&male, "(male)")], "", "{0} {1} {2}"),
+        template("Given name (female)", NameKind::Person, vec![word("adj", &[]), word("noun", &[]), marker(&female, "(female)")], "", "{0} {1} {2}"),
+        template("Epithet", NameKind::Person, vec![word("adj", &[])], "", "the {0}"),
+        template("Toponym", NameKind::Place, vec![word("adj", &[]), word("noun", &["location"])], "", "{0} {1}"),
+        template("Clan name", NameKind::Clan, vec![word("noun", &[]), word("noun", &[])], "", "{0} {1} clan"),
+        template("Polity", NameKind::Polity, vec![word("noun", &[])], "", "the realm of {0}"),
+    ]
+}
+
+impl WordGenerator {
+    pub fn name_templates(&self, inventory: &PhoneticInventory) -> Vec<NameTemplate> {
+        if self.naming.templates.is_empty() {
+            default_name_templates(inventory)
+        } else {
+            self.naming.templates.clone()
+        }
+    }
+
+    // The templates a `Name` part may be filled from; see `NamePart::Name`.
+    pub fn inner_name_templates(&self, inventory: &PhoneticInventory, kind: NameKind, name: Option<&str>) -> Vec<NameTemplate> {
+        self.name_templates(inventory).into_iter()
+            .filter(|t| t.kind == kind && name.is_none_or(|n| t.name == n))
+            .filter(|t| !t.parts.iter().any(|p| matches!(p, NamePart::Name { .. })))
+            .collect()
+    }
+
+    // Builds one name from `template` out of words in `lexicon`. Returns `None` when the
+    // lexicon has no word that fits one of the template's parts.
+    pub fn generate_name(&self, lexicon: &Lexicon, inventory: &PhoneticInventory, template: &NameTemplate) -> Option<GeneratedName> {
+        let mut rng = rand::rng();
+        let mut forms = Vec::new();
+        let mut glosses = Vec::new();
+        let mut etymology = Vec::new();
+
+        for part in &template.parts {
+            match part {
+                NamePart::Marker { form, gloss } => {
+                    forms.push(form.clone());
+                    glosses.push(gloss.clone());
+                }
+                NamePart::Name { kind, template } => {
+                    let mut templates = self.inner_name_templates(inventory, *kind, template.as_deref());
+                    templates.shuffle(&mut rng);
+                    let inner = templates.iter().find_map(|t| self.generate_name(lexicon, inventory, t))?;
+                    etymology.push(format!("{} '{}' ({})", inner.name, inner.gloss, inner.template));
+                    etymology.extend(inner.etymology);
+                    forms.push(inner.name.clone());
+                    glosses.push(inner.name);
+                }
+                NamePart::Word { pos, tags, rule, max_depth } => {
+                    let rule = match rule {
+                        Some(name) => Some(self.morphology.derivational_rules.iter().find(|r| &r.name == name)?),
+                        None => None,
+                    };
+                    let candidates: Vec<&Lexeme> = lexicon.graph.values()
+                        .filter(|l| pos.as_ref().is_none_or(|p| &l.part_of_speech == p))
+                        .filter(|l| tags.iter().all(|t| l.tags().contains(t)))
+                        .filter(|l| max_depth.is_none_or(|max| lexicon.depth_of(&l.id).unwrap_or(0) <= max))
+                        .filter(|l| rule.is_none_or(|r| {
+                            r.applies_to_pos.contains(&l.part_of_speech)
+                                && self.constraints_permit(r, l, lexicon.depth_of(&l.id).unwrap_or(0), inventory)
+                        }))
+                        .collect();
+                    // A word the lexicon really derived by the rule is used as it stands, sound
+                    // changes and all. Failing that, the rule is applied afresh with its affix
+                    // as the lexicon's words now have it.
+                    let derived: Vec<&Lexeme> = lexicon.graph.values()
+                        .filter(|l| rule.is_some_and(|r| l.origin == Origin::Derivation && l.rule_applied.as_ref() == Some(&r.name)))
+                        .filter(|l| l.parent_id.is_some_and(|p| candidates.iter().any(|c| c.id == p)))
+                        .collect();
+                    match (rule, derived.choose(&mut rng)) {
+                        (Some(rule), None) => {
+                            let base = candidates.choose(&mut rng)?;
+                            let history = self.format_etymology(lexicon, &base.id).unwrap_or_else(|| base.form.clone());
+                            let affix = current_affix(lexicon, &rule.name).unwrap_or_else(|| rule.process.affix().to_string());
+                            let (form, _, meaning) = Self::apply_rule(base, rule, &affix);
+                            etymology.push(format!("{} ← {} ({}) ← {} '{}'", form, affix, rule.name, history, base.meaning()));
+                            forms.push(form);
+                            glosses.push(meaning);
+                        }
+                        (_, derived) => {
+                            let word = match derived {
+                                Some(word) => word,
+                                None => candidates.choose(&mut rng)?,
+                            };
+                            let history = self.format_etymology(lexicon, &word.id).unwrap_or_else(|| word.form.clone());
+                            etymology.push(format!("{} '{}'", history, word.meaning()));
+                            forms.push(word.form.clone());
+                            glosses.push(word.meaning().to_string());
+                        }
+                    }
+                }
+            }
+        }
+
+        // Hyphenated pieces glue onto their neighbour; everything else is a separate word.
+        let mut words: Vec<String> = Vec::new();
+        let mut glue_next = false;
+        for form in &forms {
+            match words.last_mut() {
+                Some(last) if glue_next || form.starts_with('-') => last.push_str(form),
+                _ => words.push(form.clone()),
+            }
+            glue_next = form.ends_with('-');
+        }
+        // Affix hyphens are bookkeeping, not spelling.
+        let words: Vec<String> = words.iter().map(|w| w.replace('-', "")).collect();
+        let name = if template.separator.is_empty() {
+            capitalize(&words.concat())
+        } else {
+            words.iter().map(|w| capitalize(w)).collect::<Vec<_>>().join(&template.separator)
+        };
+
+        let gloss = match &template.gloss_template {
+            Some(gloss_template) => glosses.iter().enumerate()
+                .fold(gloss_template.clone(), |text, (i, g)| text.replace(&format!("{{{}}}", i), g)),
+            None => glosses.join(" "),
+        };
+
+        Some(GeneratedName {
+            name,
+            kind: template.kind,
+            template: template.name.clone(),
+            gloss,
+            etymology,
+        })
+    }
+}
+
+// The affix the lexicon's words built by `rule_name` most often carry now, after sound
+// changes; ties go to the alphabetically first.
+fn current_affix(lexicon: &Lexicon, rule_name: &str) -> Option<String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for lexeme in lexicon.graph.values().filter(|l| l.rule_applied.as_deref() == Some(rule_name)) {
+        if let Some(affix) = &lexeme.affix_applied {
+            *counts.entry(affix.as_str()).or_default() += 1;
+        }
+    }
+    counts.into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+        .map(|(affix, _)| affix.to_string())
+}
+
+pub fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{derived, generator, inventory, root};
+    use serde_json::json;
+
+    fn toponym_generator() -> WordGenerator {
+        generator(json!({
+            "morphology": { "derivational_rules": [{
+                "name": "Place",
+                "type": "Suffix",
+                "form": "-ko",
+                "applies_to_pos": ["noun"],
+                "output_pos": "noun",
+                "meaning_template": "place of {parent_meaning}",
+            }] },
+            "naming": { "templates": [{
+                "name": "Toponym",
+                "kind": "Place",
+                "parts": [{ "type": "Word", "pos": "noun", "rule": "Place", "max_depth": 0 }],
+            }] },
+        }))
+    }
+
+    #[test]
+    fn a_rule_made_part_uses_the_word_the_lexicon_derived() {
+        let generator = toponym_generator();
+        let mut lexicon = Lexicon::new();
+        let water = root("kado", "noun", "water", &[]);
+        lexicon.add_lexeme(derived(&water, "kado-ku", "place of water", "Place", "-ku"));
+        lexicon.add_lexeme(water);
+
+        let template = &generator.name_templates(&inventory())[0];
+        let name = generator.generate_name(&lexicon, &inventory(), template).unwrap();
+        assert_eq!(name.name, "Kadoku");
+        assert_eq!(name.gloss, "place of water");
+    }
+
+    #[test]
+    fn a_freshly_coined_part_uses_the_affix_as_it_sounds_now() {
+        let generator = toponym_generator();
+        let mut lexicon = Lexicon::new();
+        // The only noun the rule may still take; the derived word hangs off a verb.
+        let drink = root("rad", "verb", "drink", &[]);
+        lexicon.add_lexeme(derived(&drink, "rad-ku", "place of drink", "Place", "-ku"));
+        lexicon.add_lexeme(drink);
+        lexicon.add_lexeme(root("tura", "noun", "stone", &[]));
+
+        let template = &generator.name_templates(&inventory())[0];
+        let name = generator.generate_name(&lexicon, &inventory(), template).unwrap();
+        assert_eq!(name.name, "Turaku");
+    }
+
+    #[test]
+    fn default_given_names_carry_a_gender_marker() {
+        let generator = generator(json!({}));
+        let mut lexicon = Lexicon::new();
+        lexicon.add_lexeme(root("kad", "adj", "bright", &[]));
+        lexicon.add_lexeme(root("tor", "noun", "river", &[]));
+
+        let templates = generator.name_templates(&inventory());
+        let given = |name: &str| {
+            let template = templates.iter().find(|t| t.name == name).unwrap();
+            generator.generate_name(&lexicon, &inventory(), template).unwrap()
+        };
+        assert_eq!(given("Given name (male)").name, "Kadtoru");
+        assert_eq!(given("Given name (female)").name, "Kadtora");
+        assert_eq!(given("Given name (female)").gloss, "bright river (female)");
+    }
+}
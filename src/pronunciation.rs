@@ -0,0 +1,252 @@
+use crate::{Lexicon, Phoneme, PhoneticInventory, WordGenerator};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// "Sounds like" respellings for English readers, e.g. "KHAHR-uhz-ool": the form split into
+// syllables, each phoneme written the way an English speaker would spell its nearest sound,
+// and the stressed syllable in capitals.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct PronunciationSettings {
+    #[serde(default)]
+    pub stress: StressRule,
+    #[serde(default)]
+    pub approximations: HashMap<String, Approximation>, // Grapheme -> override of the built-in table.
+}
+
+// Which syllable of a word carries the stress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+pub enum StressRule {
+    #[default]
+    Initial,        // First syllable (trochaic rhythm).
+    Penultimate,    // Second to last, or the only one.
+    Final,          // Last syllable (iambic rhythm).
+}
+
+impl StressRule {
+    // The index of the stressed syllable in a word of `syllables` syllables.
+    pub fn stressed_syllable(self, syllables: usize) -> usize {
+        match self {
+            StressRule::Initial => 0,
+            StressRule::Penultimate => syllables.saturating_sub(2),
+            StressRule::Final => syllables.saturating_sub(1),
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            StressRule::Initial => "Stress falls on the first syllable.",
+            StressRule::Penultimate => "Stress falls on the second-to-last syllable.",
+            StressRule::Final => "Stress falls on the last syllable.",
+        }
+    }
+}
+
+// How to respell one phoneme for English readers, with a word that has the sound.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Approximation {
+    pub respelling: String,
+    #[serde(default)]
+    pub reduced: Option<String>, // Spelling in an unstressed syllable, e.g. "uh" for a.
+    #[serde(default)]
+    pub as_in: Option<String>,
+}
+
+// One line of the pronunciation key.
+#[derive(Debug, Clone)]
+pub struct KeyEntry {
+    pub grapheme: String,
+    pub ipa: String,
+    pub respelling: String,
+    pub as_in: String,
+}
+
+impl WordGenerator {
+    // The English approximation of a phoneme: the configured one, else the built-in table
+    // (looked up by IPA value), else the grapheme itself.
+    pub fn approximation(&self, phoneme: &Phoneme) -> Approximation {
+        if let Some(approximation) = self.pronunciation.approximations.get(phoneme.grapheme()) {
+            return approximation.clone();
+        }
+        match english_approximation(&phoneme.ipa()) {
+            Some((respelling, reduced, as_in)) => Approximation {
+                respelling: respelling.to_string(),
+                reduced: reduced.map(str::to_string),
+                as_in: Some(as_in.to_string()),
+            },
+            None => Approximation { respelling: phoneme.grapheme().to_string(), reduced: None, as_in: None },
+        }
+    }
+
+    // The respelled pronunciation of a form. Hyphens between morphemes are ignored, since
+    // they are not heard; separate words stay separate.
+    pub fn pronunciation_guide(&self, inventory: &PhoneticInventory, form: &str) -> String {
+        form.split_whitespace()
+            .map(|word| self.word_guide(inventory, word))
+            .filter(|guide| !guide.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn word_guide(&self, inventory: &PhoneticInventory, word: &str) -> String {
+        // Letters outside the inventory (such as those in hand-written affixes) are kept as
+        // they are, read as vowels if they are Latin vowels; hyphens and other marks are dropped.
+        let segments: Vec<Segment> = inventory.tokenize(word).into_iter()
+            .filter_map(|(text, phoneme)| match phoneme {
+                Some(p) => Some(Segment::Phoneme(p)),
+                None if text.chars().all(char::is_alphabetic) => Some(Segment::Unknown(text)),
+                None => None,
+            })
+            .collect();
+        let syllables = syllabify(&segments);
+        let stressed = self.pronunciation.stress.stressed_syllable(syllables.len());
+        syllables.iter().enumerate()
+            .map(|(i, syllable)| {
+                let spelled: String = syllable.iter()
+                    .map(|segment| match segment {
+                        Segment::Phoneme(p) => {
+                            let approximation = self.approximation(p);
+                            match approximation.reduced {
+                                Some(reduced) if i != stressed && syllables.len() > 1 => reduced,
+                                _ => approximation.respelling,
+                            }
+                        }
+                        Segment::Unknown(text) => text.to_string(),
+                    })
+                    .collect();
+                if i == stressed && syllables.len() > 1 { spelled.to_uppercase() } else { spelled }
+            })
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    // The pronunciation key: every phoneme of the inventory with its respelling, vowels first.
+    pub fn pronunciation_key(&self, inventory: &PhoneticInventory) -> Vec<KeyEntry> {
+        inventory.phonemes()
+            .map(|p| {
+                let approximation = self.approximation(p);
+                let respelling = match &approximation.reduced {
+                    Some(reduced) => format!("{} (unstressed: {})", approximation.respelling, reduced),
+                    None => approximation.respelling,
+                };
+                KeyEntry {
+                    grapheme: p.grapheme().to_string(),
+                    ipa: p.ipa(),
+                    respelling,
+                    as_in: approximation.as_in.unwrap_or_else(|| "no English equivalent".to_string()),
+                }
+            })
+            .collect()
+    }
+
+    // The key as a one-page Markdown document, with the stress rule and a worked example.
+    pub fn pronunciation_key_markdown(&self, inventory: &PhoneticInventory, example: Option<&str>) -> String {
+        let mut page = String::from("# Pronunciation Key\n\n");
+        page.push_str(&format!("{} The stressed syllable is written in capitals.\n\n", self.pronunciation.stress.describe()));
+        if let Some(example) = example {
+            page.push_str(&format!("Example: *{}* is said **{}**.\n\n", example, self.pronunciation_guide(inventory, example)));
+        }
+        page.push_str("| Letter | IPA | Say it as | As in |\n|---|---|---|---|\n");
+        for entry in self.pronunciation_key(inventory) {
+            page.push_str(&format!("| {} | /{}/ | {} | {} |\n", entry.grapheme, entry.ipa, entry.respelling, entry.as_in));
+        }
+        page
+    }
+
+    // A Markdown dictionary: the pronunciation key, then every word in alphabetical order
+    // with its pronunciation guide.
+    pub fn export_dictionary(&self, inventory: &PhoneticInventory, lexicon: &Lexicon) -> String {
+        let mut entries: Vec<_> = lexicon.graph.values().collect();
+        entries.sort_by(|a, b| a.form.cmp(&b.form));
+        let example = entries.iter().find(|l| l.parent_id.is_some()).or(entries.first()).map(|l| l.form.as_str());
+
+        let mut dictionary = self.pronunciation_key_markdown(inventory, example);
+        dictionary.push_str("\n# Dictionary\n\n");
+        for lexeme in entries {
+            dictionary.push_str(&format!("**{}** [{}] *{}* — {}\n\n",
+                lexeme.form, self.pronunciation_guide(inventory, &lexeme.form), lexeme.part_of_speech, lexeme.gloss_list()));
+        }
+        dictionary
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Segment<'a> {
+    Phoneme(&'a Phoneme),
+    Unknown(&'a str),
+}
+
+impl Segment<'_> {
+    fn is_vowel(&self) -> bool {
+        match self {
+            Segment::Phoneme(p) => p.is_vowel(),
+            Segment::Unknown(text) => text.chars().all(|c| "aeiou".contains(c)),
+        }
+    }
+}
+
+// Splits a word into syllables around its vowels. Consonants before the first vowel and after
+// the last stay with those syllables; between two vowels a single consonant starts the next
+// syllable, and a cluster leaves its first consonant behind ("kar-zul", "ka-rul").
+fn syllabify<'a>(segments: &[Segment<'a>]) -> Vec<Vec<Segment<'a>>> {
+    let nuclei: Vec<usize> = segments.iter().enumerate().filter(|(_, s)| s.is_vowel()).map(|(i, _)| i).collect();
+    if nuclei.is_empty() {
+        return if segments.is_empty() { Vec::new() } else { vec![segments.to_vec()] };
+    }
+    let mut starts = vec![0];
+    for pair in nuclei.windows(2) {
+        let cluster = pair[1] - pair[0] - 1;
+        starts.push(if cluster >= 2 { pair[0] + 2 } else { pair[1] - cluster });
+    }
+    starts.iter().enumerate()
+        .map(|(i, &start)| {
+            let end = starts.get(i + 1).copied().unwrap_or(segments.len());
+            segments[start..end].to_vec()
+        })
+        .collect()
+}
+
+// English respellings by IPA value: (respelling, unstressed respelling, example).
+fn english_approximation(ipa: &str) -> Option<(&'static str, Option<&'static str>, &'static str)> {
+    Some(match ipa {
+        "a" | "ɑ" => ("ah", Some("uh"), "father"),
+        "æ" => ("a", None, "cat"),
+        "e" | "ɛ" => ("eh", Some("ih"), "bed"),
+        "i" => ("ee", None, "see"),
+        "ɪ" => ("ih", None, "sit"),
+        "o" | "ɔ" => ("oh", None, "go"),
+        "u" => ("oo", None, "food"),
+        "ʊ" => ("uu", None, "book"),
+        "ə" | "ʌ" => ("uh", None, "about"),
+        "y" => ("ew", None, "French tu"),
+        "p" => ("p", None, "pin"),
+        "b" => ("b", None, "bin"),
+        "t" => ("t", None, "tin"),
+        "d" => ("d", None, "din"),
+        "k" => ("k", None, "kin"),
+        "ɡ" => ("g", None, "go"),
+        "q" => ("k", None, "a k said far back in the throat"),
+        "m" => ("m", None, "man"),
+        "n" => ("n", None, "no"),
+        "ŋ" => ("ng", None, "sing"),
+        "f" => ("f", None, "fun"),
+        "v" => ("v", None, "van"),
+        "θ" => ("th", None, "thin"),
+        "ð" => ("dh", None, "this"),
+        "s" => ("s", None, "sun"),
+        "z" => ("z", None, "zoo"),
+        "ʃ" => ("sh", None, "ship"),
+        "ʒ" => ("zh", None, "measure"),
+        "tʃ" => ("ch", None, "chin"),
+        "dʒ" => ("j", None, "jam"),
+        "x" => ("kh", None, "Scottish loch"),
+        "ɣ" => ("gh", None, "a g without closing the throat"),
+        "h" => ("h", None, "hat"),
+        "l" => ("l", None, "let"),
+        "r" | "ɹ" => ("r", None, "run"),
+        "ɾ" => ("r", None, "the tt in American better"),
+        "ʀ" | "ʁ" => ("rr", None, "French rue"),
+        "j" => ("y", None, "yes"),
+        "w" => ("w", None, "wet"),
+        _ => return None,
+    })
+}
@@ -0,0 +1,108 @@
+use crate::{Lexeme, Lexicon};
+use regex::Regex;
+
+// How a query matches against `Lexeme.form`.
+#[derive(Debug, Clone)]
+pub enum FormMatch {
+    Exact(String),
+    Prefix(String),
+    Regex(Regex),
+}
+
+impl FormMatch {
+    fn matches(&self, form: &str) -> bool {
+        match self {
+            FormMatch::Exact(target) => form == target,
+            FormMatch::Prefix(prefix) => form.starts_with(prefix.as_str()),
+            FormMatch::Regex(re) => re.is_match(form),
+        }
+    }
+}
+
+// The order results come back in, like the two halves of a bilingual dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Form,  // conlang -> English
+    Gloss, // English -> conlang (the "reverse dictionary")
+}
+
+// A set of filters over a `Lexicon`. Every field left as `None` matches everything.
+#[derive(Debug, Clone, Default)]
+pub struct LexiconQuery {
+    pub form: Option<FormMatch>,
+    pub meaning_contains: Option<String>, // Case-insensitive substring of the English gloss.
+    pub part_of_speech: Option<String>,
+    pub rule_applied: Option<String>,
+    pub min_depth: Option<usize>,         // 0 = roots, 1 = first derivation, ...
+    pub max_depth: Option<usize>,
+    pub sort: SortOrder,
+}
+
+impl LexiconQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn matches(&self, lexicon: &Lexicon, lexeme: &Lexeme) -> bool {
+        if let Some(form) = &self.form
+            && !form.matches(&lexeme.form) {
+            return false;
+        }
+        if let Some(needle) = &self.meaning_contains
+            && !lexeme.senses.iter().any(|s| s.gloss.to_lowercase().contains(&needle.to_lowercase())) {
+            return false;
+        }
+        if let Some(pos) = &self.part_of_speech
+            && &lexeme.part_of_speech != pos {
+            return false;
+        }
+        if let Some(rule) = &self.rule_applied
+            && lexeme.rule_applied.as_ref() != Some(rule) {
+            return false;
+        }
+        if self.min_depth.is_some() || self.max_depth.is_some() {
+            let depth = lexicon.depth_of(&lexeme.id).unwrap_or(0);
+            if self.min_depth.is_some_and(|min| depth < min) || self.max_depth.is_some_and(|max| depth > max) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl Lexicon {
+    // Number of derivation steps between a lexeme and its root (roots are depth 0).
+    pub fn depth_of(&self, id: &uuid::Uuid) -> Option<usize> {
+        self.graph.get(id)?;
+        Some(self.ancestors(id).len())
+    }
+
+    pub fn search(&self, query: &LexiconQuery) -> Vec<&Lexeme> {
+        let mut results: Vec<&Lexeme> = self.graph.values()
+            .filter(|l| query.matches(self, l))
+            .collect();
+
+        match query.sort {
+            SortOrder::Form => results.sort_by(|a, b| {
+                a.form.cmp(&b.form).then_with(|| a.meaning().cmp(b.meaning()))
+            }),
+            SortOrder::Gloss => results.sort_by(|a, b| {
+                a.meaning().to_lowercase().cmp(&b.meaning().to_lowercase()).then_with(|| a.form.cmp(&b.form))
+            }),
+        }
+        results
+    }
+
+    pub fn find_by_form(&self, form: &str) -> Vec<&Lexeme> {
+        self.search(&LexiconQuery { form: Some(FormMatch::Exact(form.to_string())), ..Default::default() })
+    }
+
+    pub fn find_by_meaning(&self, meaning: &str) -> Vec<&Lexeme> {
+        self.search(&LexiconQuery {
+            meaning_contains: Some(meaning.to_string()),
+            sort: SortOrder::Gloss,
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{DerivationProcess, FormChange, Lexeme, Origin, Phoneme, PhoneticInventory, SoundChange, WordGenerator};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use serde::Deserialize;
+
+// Variation by social stratum. Court speech keeps older pronunciations (where a word's history
+// shows it had them) and marks nouns and
+// verbs with honorifics; slang clips words and coins new derivations; the common register is
+// the lexicon as generated. Variants are worked out from the word itself each time they are
+// asked for, seeded by its id, so a word always has the same variant in a given register.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegisterSettings {
+    #[serde(default)]
+    pub weights: RegisterWeights,
+    #[serde(default)]
+    pub archaisms: Vec<SoundChange>,  // Each undoes a sound change, "current > older", in the words it struck.
+    #[serde(default)]
+    pub honorifics: Vec<Honorific>,
+    #[serde(default = "default_slang_rate")]
+    pub slang_rate: f64,              // Chance (0.0 - 1.0) that a word has a slang variant at all.
+    #[serde(default)]
+    pub slang_affixes: Vec<DerivationProcess>, // Affixes for novel slang derivations.
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum Register {
+    Formal,
+    Common,
+    Slang,
+}
+
+impl Register {
+    pub const ALL: [Register; 3] = [Register::Formal, Register::Common, Register::Slang];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Register::Formal => "formal",
+            Register::Common => "common",
+            Register::Slang => "slang",
+        }
+    }
+}
+
+// How often a sentence is spoken in each register.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RegisterWeights {
+    pub formal: f64,
+    pub common: f64,
+    pub slang: f64,
+}
+
+impl RegisterWeights {
+    pub fn of(&self, register: Register) -> f64 {
+        match register {
+            Register::Formal => self.formal,
+            Register::Common => self.common,
+            Register::Slang => self.slang,
+        }
+    }
+}
+
+// An affix court speech adds to every word of the given parts of speech, e.g. a prefix "ka-" on nouns.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Honorific {
+    pub applies_to_pos: Vec<String>,
+    #[serde(flatten)]
+    pub process: DerivationProcess,
+}
+
+// One word as spoken in one register, with the processes that made it differ from the common form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegisterVariant {
+    pub register: Register,
+    pub form: String,
+    pub processes: Vec<String>, // e.g. ["archaic o > au", "honorific ka-"]
+}
+
+fn default_slang_rate() -> f64 { 0.5 }
+
+impl Default for RegisterSettings {
+    fn default() -> Self {
+        Self {
+            weights: RegisterWeights::default(),
+            archaisms: Vec::new(),
+            honorifics: Vec::new(),
+            slang_rate: default_slang_rate(),
+            slang_affixes: Vec::new(),
+        }
+    }
+}
+
+impl Default for RegisterWeights {
+    fn default() -> Self {
+        Self { formal: 0.2, common: 0.6, slang: 0.2 }
+    }
+}
+
+impl WordGenerator {
+    // A register chosen by `registers.weights`, falling back to the common register.
+    pub fn pick_register(&self) -> Register {
+        let weights = &self.registers.weights;
+        Register::ALL.choose_weighted(&mut rand::rng(), |r| weights.of(*r).max(0.0))
+            .copied()
+            .unwrap_or(Register::Common)
+    }
+
+    // The word in every register, formal first.
+    pub fn register_variants(&self, inventory: &PhoneticInventory, lexeme: &Lexeme) -> Vec<RegisterVariant> {
+        Register::ALL.iter().map(|r| self.register_variant(inventory, lexeme, *r)).collect()
+    }
+
+    pub fn register_variant(&self, inventory: &PhoneticInventory, lexeme: &Lexeme, register: Register) -> RegisterVariant {
+        let mut variant = RegisterVariant { register, form: lexeme.form.clone(), processes: Vec::new() };
+        match register {
+            Register::Common => {}
+            Register::Formal => {
+                let (form, undone) = self.archaic_form(inventory, lexeme);
+                variant.form = form;
+                variant.processes.extend(undone.iter().map(|change| format!("archaic {}", change.describe())));
+                if let Some(honorific) = self.registers.honorifics.iter().find(|h| h.applies_to_pos.contains(&lexeme.part_of_speech)) {
+                    variant.form = attach(&honorific.process, &variant.form);
+                    variant.processes.push(format!("honorific {}", honorific.process.affix()));
+                }
+            }
+            Register::Slang => {
+                // Idioms are already colourful; slang leaves them alone.
+                let mut rng = StdRng::seed_from_u64(lexeme.id.as_u64_pair().0);
+                if lexeme.origin == Origin::Idiom || !rng.random_bool(self.registers.slang_rate.clamp(0.0, 1.0)) {
+                    return variant;
+                }
+                let mut options: Vec<(String, String)> = Vec::new();
+                if let Some(clipped) = self.clip(inventory, &lexeme.form) {
+                    options.push((clipped, "clipped".to_string()));
+                }
+                for affix in &self.registers.slang_affixes {
+                    let base = self.clip(inventory, &lexeme.form).unwrap_or_else(|| lexeme.form.clone());
+                    let form = attach(affix, &base);
+                    if !self.contains_illegal_patterns(&form) {
+                        options.push((form, format!("slang {}", affix.affix())));
+                    }
+                }
+                if let Some((form, process)) = options.choose(&mut rng) {
+                    variant.form = form.clone();
+                    variant.processes.push(process.clone());
+                }
+            }
+        }
+        variant
+    }
+
+    // The word before the sound changes an archaism undoes, with every other change since
+    // replayed on top. Only changes in the word's own history count, so a word that always
+    // had the newer sound keeps it; nor does anything before the word was last rebuilt.
+    // Returns the form and the archaisms that changed it.
+    fn archaic_form(&self, inventory: &PhoneticInventory, lexeme: &Lexeme) -> (String, Vec<&SoundChange>) {
+        let undoing = |change: &FormChange| match change {
+            FormChange::Sound(sound) => self.registers.archaisms.iter().find(|a| a.from == sound.to && a.to == sound.from),
+            FormChange::Replaced | FormChange::Restemmed => None,
+        };
+        let history = &lexeme.form_changes;
+        let start = history.iter().rposition(|(change, _)| !matches!(change, FormChange::Sound(_))).map_or(0, |i| i + 1);
+        let Some(first) = (start..history.len()).find(|&i| undoing(&history[i].0).is_some()) else {
+            return (lexeme.form.clone(), Vec::new());
+        };
+
+        let mut form = history[first].1.clone();
+        let mut undone = Vec::new();
+        for (change, _) in &history[first..] {
+            match undoing(change) {
+                Some(archaism) => {
+                    if !undone.contains(&archaism) {
+                        undone.push(archaism);
+                    }
+                }
+                None => {
+                    if let FormChange::Sound(sound) = change {
+                        form = sound.apply(inventory, &form);
+                    }
+                }
+            }
+        }
+        (form, undone)
+    }
+
+    // Cuts the stem of a word (its longest part between hyphens, so affixes fall away) down to
+    // its first syllable, closed by the next consonant if there is one: "az-gorbad" -> "gor".
+    // Returns `None` if the stem is a single syllable, the result would be a lone sound, or it
+    // breaks the illegal patterns.
+    fn clip(&self, inventory: &PhoneticInventory, form: &str) -> Option<String> {
+        if form.contains(' ') {
+            return None;
+        }
+        let stem = form.split('-').max_by_key(|part| inventory.count_syllables(part))?;
+        if inventory.count_syllables(stem) < 2 {
+            return None;
+        }
+        let mut clipped = Vec::new();
+        for phoneme in inventory.segment(stem) {
+            if clipped.iter().any(|p: &&Phoneme| p.is_vowel()) {
+                if !phoneme.is_vowel() {
+                    clipped.push(phoneme);
+                }
+                break;
+            }
+            clipped.push(phoneme);
+        }
+        let clipped: String = clipped.iter().map(|p| p.grapheme()).collect();
+        (inventory.segment(&clipped).len() >= 2 && !self.contains_illegal_patterns(&clipped)).then_some(clipped)
+    }
+}
+
+fn attach(process: &DerivationProcess, form: &str) -> String {
+    match process {
+        DerivationProcess::Prefix { form: affix } => format!("{}{}", affix, form),
+        DerivationProcess::Suffix { form: affix } => format!("{}{}", form, affix),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{generator, inventory, root};
+    use crate::{Lexicon, SoundEnvironment};
+    use serde_json::json;
+
+    #[test]
+    fn archaisms_restore_only_sounds_a_word_really_lost() {
+        let generator = generator(json!({ "registers": { "archaisms": [{ "from": "u", "to": "o", "environment": "WordFinal" }] } }));
+        let inventory = inventory();
+        let mut lexicon = Lexicon::new();
+        let changed = root("kado", "noun", "water", &[]);
+        let always = root("gadu", "noun", "stone", &[]);
+        let (changed_id, always_id) = (changed.id, always.id);
+        lexicon.add_lexeme(changed);
+        lexicon.add_lexeme(always);
+        let change = |from: &str, to: &str, environment| SoundChange { from: from.to_string(), to: to.to_string(), environment };
+        lexicon.apply_sound_change(&inventory, &change("o", "u", SoundEnvironment::WordFinal));
+        lexicon.apply_sound_change(&inventory, &change("d", "t", SoundEnvironment::Anywhere));
+
+        // "kado" became "kadu", then "katu": the court says "kato", keeping the later d > t.
+        let formal = generator.register_variant(&inventory, &lexicon.graph[&changed_id], Register::Formal);
+        assert_eq!(formal.form, "kato");
+        assert_eq!(formal.processes, ["archaic u > o / _#"]);
+        // "gadu" always ended in u, so there is nothing older to restore.
+        let formal = generator.register_variant(&inventory, &lexicon.graph[&always_id], Register::Formal);
+        assert_eq!(formal.form, "gatu");
+        assert!(formal.processes.is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{Lexicon, Phoneme, PhoneticInventory};
+
+// A way of spelling the language. Lexemes are stored in the native spelling (each phoneme's
+// grapheme); the others are worked out phoneme by phoneme.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scheme {
+    Native,
+    Ipa,
+    Named(String), // A romanization, configured per phoneme or one of BUILTIN_SCHEMES.
+}
+
+// Romanizations every language has, derived from the graphemes unless a phoneme overrides them:
+// "scholarly" writes digraphs as single letters with diacritics (sh -> š), "ascii" strips any
+// diacritics from the native spelling, and "popular" is ASCII respelled for English readers.
+pub const BUILTIN_SCHEMES: &[&str] = &["scholarly", "ascii", "popular"];
+
+impl Scheme {
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "native" => Scheme::Native,
+            "ipa" => Scheme::Ipa,
+            other => Scheme::Named(other.to_string()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Scheme::Native => "native",
+            Scheme::Ipa => "ipa",
+            Scheme::Named(name) => name,
+        }
+    }
+}
+
+impl Phoneme {
+    // The configured IPA value, or a guess from the grapheme for the common ones.
+    pub fn ipa(&self) -> String {
+        if let Some(ipa) = &self.ipa {
+            return ipa.clone();
+        }
+        default_ipa(&self.grapheme).map_or_else(|| self.grapheme.replace('g', "\u{261}"), str::to_string)
+    }
+
+    // How this phoneme is written in `scheme`. A romanization the phoneme does not configure
+    // falls back to the built-in rule of that name, then to the native grapheme.
+    pub fn spelling(&self, scheme: &Scheme) -> String {
+        match scheme {
+            Scheme::Native => self.grapheme.clone(),
+            Scheme::Ipa => self.ipa(),
+            Scheme::Named(name) => match self.romanizations.get(name) {
+                Some(spelling) => spelling.clone(),
+                None => match name.as_str() {
+                    "scholarly" => scholarly(&self.grapheme).map_or_else(|| self.grapheme.clone(), str::to_string),
+                    "ascii" => Some(ascii_fold(&self.grapheme)).filter(|s| !s.is_empty()).unwrap_or_else(|| self.grapheme.clone()),
+                    "popular" => Some(popular(&ascii_fold(&self.grapheme))).filter(|s| !s.is_empty()).unwrap_or_else(|| self.grapheme.clone()),
+                    _ => self.grapheme.clone(),
+                },
+            },
+        }
+    }
+}
+
+impl PhoneticInventory {
+    // Every scheme this inventory can be spelled in: native, IPA, the built-in romanizations
+    // and any other romanization a phoneme names.
+    pub fn schemes(&self) -> Vec<Scheme> {
+        let mut names: Vec<&str> = BUILTIN_SCHEMES.to_vec();
+        for phoneme in self.phonemes() {
+            for name in phoneme.romanizations.keys() {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+        }
+        names[BUILTIN_SCHEMES.len()..].sort();
+        [Scheme::Native, Scheme::Ipa].into_iter()
+            .chain(names.into_iter().map(|n| Scheme::Named(n.to_string())))
+            .collect()
+    }
+
+    pub fn scheme(&self, name: &str) -> Option<Scheme> {
+        self.schemes().into_iter().find(|s| s.name() == name)
+    }
+
+    // Like `tokenize`, but reading `form` as spelled in `scheme`, longest spelling first.
+    pub fn tokenize_in<'a>(&self, form: &'a str, scheme: &Scheme) -> Vec<(&'a str, Option<&Phoneme>)> {
+        let spellings: Vec<(String, &Phoneme)> = self.phonemes()
+            .map(|p| (p.spelling(scheme), p))
+            .filter(|(s, _)| !s.is_empty())
+            .collect();
+        let mut tokens = Vec::new();
+        let mut rest = form;
+        while !rest.is_empty() {
+            let longest = spellings.iter()
+                .filter(|(s, _)| rest.starts_with(s.as_str()))
+                .max_by_key(|(s, _)| s.len());
+            let len = match longest {
+                Some((spelling, _)) => spelling.len(),
+                None => rest.chars().next().map_or(1, |c| c.len_utf8()),
+            };
+            tokens.push((&rest[..len], longest.map(|(_, p)| *p)));
+            rest = &rest[len..];
+        }
+        tokens
+    }
+
+    // Respells a form from one scheme into another. Anything that is not a phoneme in
+    // `from` (hyphens, spaces, stray letters) is copied unchanged.
+    pub fn convert(&self, form: &str, from: &Scheme, to: &Scheme) -> String {
+        self.tokenize_in(form, from).into_iter()
+            .map(|(text, phoneme)| phoneme.map_or_else(|| text.to_string(), |p| p.spelling(to)))
+            .collect()
+    }
+
+    // Pairs of phonemes that `scheme` spells the same way, which makes it impossible to
+    // convert out of that scheme.
+    pub fn ambiguous_spellings(&self, scheme: &Scheme) -> Vec<(String, String, String)> {
+        let phonemes: Vec<&Phoneme> = self.phonemes().collect();
+        let mut clashes = Vec::new();
+        for (i, a) in phonemes.iter().enumerate() {
+            for b in &phonemes[i + 1..] {
+                if a.spelling(scheme) == b.spelling(scheme) {
+                    clashes.push((a.grapheme.clone(), b.grapheme.clone(), a.spelling(scheme)));
+                }
+            }
+        }
+        clashes
+    }
+}
+
+impl Lexicon {
+    // A copy of the lexicon with every form (and affix) spelled in `scheme`, for display
+    // and export; meanings and structure are untouched.
+    pub fn respelled(&self, inventory: &PhoneticInventory, scheme: &Scheme) -> Lexicon {
+        let mut lexicon = self.clone();
+        if *scheme == Scheme::Native {
+            return lexicon;
+        }
+        let convert = |form: &str| inventory.convert(form, &Scheme::Native, scheme);
+        for lexeme in lexicon.graph.values_mut() {
+            lexeme.form = convert(&lexeme.form);
+            if let Some(affix) = &lexeme.affix_applied {
+                lexeme.affix_applied = Some(convert(affix));
+            }
+            for (_, old_form) in &mut lexeme.form_changes {
+                *old_form = convert(old_form);
+            }
+        }
+        for collision in &mut lexicon.collisions {
+            collision.form = convert(&collision.form);
+        }
+        lexicon
+    }
+}
+
+fn default_ipa(grapheme: &str) -> Option<&'static str> {
+    Some(match grapheme {
+        "th" => "θ",
+        "dh" => "ð",
+        "sh" => "ʃ",
+        "zh" => "ʒ",
+        "ch" => "tʃ",
+        "j" => "dʒ",
+        "ng" => "ŋ",
+        "kh" => "x",
+        "gh" => "ɣ",
+        "y" => "j",
+        "c" => "k",
+        _ => return None,
+    })
+}
+
+fn scholarly(grapheme: &str) -> Option<&'static str> {
+    Some(match grapheme {
+        "th" => "ṯ",
+        "dh" => "ḏ",
+        "sh" => "š",
+        "zh" => "ž",
+        "ch" => "č",
+        "j" => "ǧ",
+        "ng" => "ŋ",
+        "kh" => "ḫ",
+        "gh" => "ġ",
+        _ => return None,
+    })
+}
+
+// Drops diacritics, spelling the letters that have no plain equivalent as digraphs. Letters
+// it cannot fold are dropped; a phoneme left with nothing keeps its grapheme.
+fn ascii_fold(spelling: &str) -> String {
+    spelling.chars()
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => "a".to_string(),
+            'è' | 'é' | 'ê' | 'ë' | 'ē' => "e".to_string(),
+            'ì' | 'í' | 'î' | 'ï' | 'ī' => "i".to_string(),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ō' | 'ø' => "o".to_string(),
+            'ù' | 'ú' | 'û' | 'ü' | 'ū' => "u".to_string(),
+            'ñ' => "n".to_string(),
+            'ç' => "c".to_string(),
+            'š' => "sh".to_string(),
+            'ž' => "zh".to_string(),
+            'č' => "ch".to_string(),
+            'ŋ' => "ng".to_string(),
+            'þ' => "th".to_string(),
+            'ð' => "dh".to_string(),
+            c if c.is_ascii() => c.to_string(),
+            _ => String::new(),
+        })
+        .collect::<String>()
+}
+
+// Respells the letters English readers are most likely to misread.
+fn popular(ascii: &str) -> String {
+    match ascii {
+        "x" => "kh".to_string(),
+        "q" => "k".to_string(),
+        "c" => "k".to_string(),
+        other => other.to_string(),
+    }
+}
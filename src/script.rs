@@ -0,0 +1,484 @@
+use crate::{ConfigError, PhoneticInventory, WordGenerator};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+// How the language is written. Glyphs are built from strokes joining anchor points on a
+// square grid, each new stroke starting where an earlier one ended so signs stay connected.
+// Everything is drawn from `seed`, so the same settings always give the same script.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScriptSettings {
+    #[serde(default)]
+    pub kind: ScriptKind,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub strokes: StrokeWeights,
+    #[serde(default = "default_grid")]
+    pub grid: u8,          // Anchor points per side of the glyph.
+    #[serde(default = "default_min_strokes")]
+    pub min_strokes: usize,
+    #[serde(default = "default_max_strokes")]
+    pub max_strokes: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+pub enum ScriptKind {
+    #[default]
+    Alphabet,  // One letter per phoneme.
+    Abjad,     // Consonants only; vowels are left for the reader to supply.
+    Abugida,   // Consonant letters carry an inherent vowel; other vowels are marks on them.
+    Syllabary, // One sign per consonant-vowel syllable, plus lone vowels and consonants.
+}
+
+impl ScriptKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ScriptKind::Alphabet => "alphabet",
+            ScriptKind::Abjad => "abjad",
+            ScriptKind::Abugida => "abugida",
+            ScriptKind::Syllabary => "syllabary",
+        }
+    }
+}
+
+// How often each stroke primitive is drawn.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StrokeWeights {
+    pub line: f64,
+    pub arc: f64,
+    #[serde(rename = "loop")]
+    pub loop_: f64,
+    pub dot: f64,
+}
+
+impl Default for StrokeWeights {
+    fn default() -> Self {
+        Self { line: 3.0, arc: 2.0, loop_: 0.5, dot: 0.5 }
+    }
+}
+
+fn default_grid() -> u8 { 3 }
+fn default_min_strokes() -> usize { 2 }
+fn default_max_strokes() -> usize { 4 }
+
+impl Default for ScriptSettings {
+    fn default() -> Self {
+        Self {
+            kind: ScriptKind::default(),
+            seed: 0,
+            strokes: StrokeWeights::default(),
+            grid: default_grid(),
+            min_strokes: default_min_strokes(),
+            max_strokes: default_max_strokes(),
+        }
+    }
+}
+
+// A point on the glyph grid, (column, row) from the top left.
+pub type Anchor = (u8, u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Stroke {
+    Line { from: Anchor, to: Anchor },
+    Arc { from: Anchor, to: Anchor, bulge: i8 }, // Bows to the left (-1) or right (1) of from -> to.
+    Loop { at: Anchor },                         // A small closed ring.
+    Dot { at: Anchor },
+}
+
+impl Stroke {
+    // The same stroke written the same way whichever end it was drawn from.
+    fn canonical(self) -> Self {
+        match self {
+            Stroke::Line { from, to } if to < from => Stroke::Line { from: to, to: from },
+            Stroke::Arc { from, to, bulge } if to < from => Stroke::Arc { from: to, to: from, bulge: -bulge },
+            stroke => stroke,
+        }
+    }
+
+    fn ends(self) -> Vec<Anchor> {
+        match self {
+            Stroke::Line { from, to } | Stroke::Arc { from, to, .. } => vec![from, to],
+            Stroke::Loop { at } | Stroke::Dot { at } => vec![at],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glyph {
+    pub grid: u8,
+    pub strokes: Vec<Stroke>, // Canonical and sorted, so equal shapes compare equal.
+}
+
+// Where an abugida's dependent sign sits relative to its consonant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkPosition {
+    Above,
+    Below,
+    After,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignKind {
+    Consonant,
+    Vowel,
+    Syllable,
+    VowelMark(MarkPosition), // Abugida: replaces the inherent vowel.
+    Virama(MarkPosition),    // Abugida: silences the inherent vowel.
+}
+
+#[derive(Debug, Clone)]
+pub struct Sign {
+    pub value: String, // What the sign writes: a grapheme or a syllable; empty for a virama.
+    pub kind: SignKind,
+    pub glyph: Glyph,
+}
+
+#[derive(Debug, Clone)]
+pub struct WritingSystem {
+    pub kind: ScriptKind,
+    pub seed: u64,
+    pub inherent_vowel: Option<String>, // Abugida only.
+    pub signs: Vec<Sign>,
+}
+
+// Glyphs are drawn in a GLYPH_SIZE square with this much margin around the grid.
+pub const GLYPH_SIZE: f64 = 100.0;
+const MARGIN: f64 = 20.0;
+pub const STROKE_WIDTH: f64 = 6.0;
+pub const LOOP_RADIUS: f64 = 9.0;
+pub const DOT_RADIUS: f64 = 5.0;
+const MAX_GLYPH_ATTEMPTS: usize = 200;
+const MAX_EXTRA_STROKES: usize = 4; // How far past max_strokes a sign may grow before giving up.
+
+impl WordGenerator {
+    // Builds the writing system described by `script` for this inventory. Fails when the
+    // stroke settings run out of distinct glyphs before every sign has one.
+    pub fn generate_script(&self, inventory: &PhoneticInventory) -> Result<WritingSystem, ConfigError> {
+        let settings = &self.script;
+        let consonants: Vec<&str> = inventory.consonants.iter().map(|p| p.grapheme()).collect();
+        let vowels: Vec<&str> = inventory.vowels.iter().map(|p| p.grapheme()).collect();
+        let needed = match settings.kind {
+            ScriptKind::Alphabet => vowels.len() + consonants.len(),
+            ScriptKind::Abjad => consonants.len(),
+            ScriptKind::Abugida => consonants.len() + 2 * vowels.len(),
+            ScriptKind::Syllabary => vowels.len() + consonants.len() * (vowels.len() + 1),
+        };
+        let mut rng = StdRng::seed_from_u64(settings.seed);
+        let mut used = HashSet::new();
+        let mut signs = Vec::new();
+        let mut add = |rng: &mut StdRng, value: String, kind: SignKind, strokes: (usize, usize)| {
+            let glyph = new_glyph(rng, settings, strokes, &mut used).ok_or_else(|| ConfigError::ScriptGlyphs(format!(
+                "only {} distinct glyphs could be drawn, but the {} needs {}; allow more strokes per sign, a larger grid or more kinds of stroke",
+                signs.len(), settings.kind.label(), needed,
+            )))?;
+            signs.push(Sign { value, kind, glyph });
+            Ok::<(), ConfigError>(())
+        };
+        let letter = (settings.min_strokes.max(1), settings.max_strokes.max(settings.min_strokes).max(1));
+        let mark = (1, 2);
+
+        let mut inherent_vowel = None;
+        match settings.kind {
+            ScriptKind::Alphabet => {
+                for v in &vowels {
+                    add(&mut rng, v.to_string(), SignKind::Vowel, letter)?;
+                }
+                for c in &consonants {
+                    add(&mut rng, c.to_string(), SignKind::Consonant, letter)?;
+                }
+            }
+            ScriptKind::Abjad => {
+                for c in &consonants {
+                    add(&mut rng, c.to_string(), SignKind::Consonant, letter)?;
+                }
+            }
+            ScriptKind::Abugida => {
+                inherent_vowel = vowels.first().map(|v| v.to_string());
+                for c in &consonants {
+                    add(&mut rng, c.to_string(), SignKind::Consonant, letter)?;
+                }
+                for v in &vowels {
+                    add(&mut rng, v.to_string(), SignKind::Vowel, letter)?;
+                }
+                let positions = [MarkPosition::Above, MarkPosition::Below, MarkPosition::After];
+                for v in vowels.iter().skip(1) {
+                    let position = *positions.choose(&mut rng).unwrap();
+                    add(&mut rng, v.to_string(), SignKind::VowelMark(position), mark)?;
+                }
+                add(&mut rng, String::new(), SignKind::Virama(MarkPosition::Below), mark)?;
+            }
+            ScriptKind::Syllabary => {
+                for v in &vowels {
+                    add(&mut rng, v.to_string(), SignKind::Vowel, letter)?;
+                }
+                for c in &consonants {
+                    for v in &vowels {
+                        add(&mut rng, format!("{}{}", c, v), SignKind::Syllable, letter)?;
+                    }
+                }
+                // Codas and clusters still need writing; these are the bare consonants.
+                for c in &consonants {
+                    add(&mut rng, c.to_string(), SignKind::Consonant, mark)?;
+                }
+            }
+        }
+
+        Ok(WritingSystem { kind: settings.kind, seed: settings.seed, inherent_vowel, signs })
+    }
+}
+
+// A glyph with a stroke count in `strokes` whose shape no earlier sign has. When the grid
+// runs short of fresh shapes, glyphs are allowed one more stroke, up to MAX_EXTRA_STROKES;
+// `None` once even that finds nothing new.
+fn new_glyph(rng: &mut StdRng, settings: &ScriptSettings, strokes: (usize, usize), used: &mut HashSet<Vec<Stroke>>) -> Option<Glyph> {
+    let grid = settings.grid.max(2);
+    let (min, max) = strokes;
+    for max in max..=max + MAX_EXTRA_STROKES {
+        for _ in 0..MAX_GLYPH_ATTEMPTS {
+            let count = rng.random_range(min..=max);
+            let mut glyph = Vec::new();
+            for _ in 0..count {
+                let stroke = random_stroke(rng, &settings.strokes, grid, &glyph);
+                if !glyph.contains(&stroke) {
+                    glyph.push(stroke);
+                }
+            }
+            glyph.sort();
+            if used.insert(glyph.clone()) {
+                return Some(Glyph { grid, strokes: glyph });
+            }
+        }
+    }
+    None
+}
+
+// A stroke starting at an end of one already drawn (anywhere, for the first), so the glyph
+// reads as one connected shape. Dots may float free.
+fn random_stroke(rng: &mut StdRng, weights: &StrokeWeights, grid: u8, drawn: &[Stroke]) -> Stroke {
+    let kinds = [(0, weights.line), (1, weights.arc), (2, weights.loop_), (3, weights.dot)];
+    let kind = kinds.choose_weighted(rng, |(_, w)| w.max(0.0)).map_or(0, |(k, _)| *k);
+    let anywhere = |rng: &mut StdRng| (rng.random_range(0..grid), rng.random_range(0..grid));
+    let ends: Vec<Anchor> = drawn.iter().flat_map(|s| s.ends()).collect();
+    let start = ends.choose(rng).copied().unwrap_or_else(|| anywhere(rng));
+
+    let stroke = match kind {
+        2 => Stroke::Loop { at: start },
+        3 => Stroke::Dot { at: anywhere(rng) },
+        _ => {
+            let mut end = anywhere(rng);
+            while end == start {
+                end = anywhere(rng);
+            }
+            if kind == 0 {
+                Stroke::Line { from: start, to: end }
+            } else {
+                Stroke::Arc { from: start, to: end, bulge: if rng.random_bool(0.5) { 1 } else { -1 } }
+            }
+        }
+    };
+    stroke.canonical()
+}
+
+impl Glyph {
+    // Where an anchor sits in a GLYPH_SIZE square, y pointing down.
+    pub fn point(&self, (x, y): Anchor) -> (f64, f64) {
+        let step = (GLYPH_SIZE - 2.0 * MARGIN) / f64::from(self.grid - 1);
+        (MARGIN + step * f64::from(x), MARGIN + step * f64::from(y))
+    }
+
+    // SVG elements drawing the glyph scaled by `scale` with its top left corner at (x, y).
+    pub fn svg_elements(&self, x: f64, y: f64, scale: f64) -> String {
+        let at = |p: Anchor| {
+            let (px, py) = self.point(p);
+            (x + px * scale, y + py * scale)
+        };
+        let width = STROKE_WIDTH * scale;
+        let mut svg = String::new();
+        for stroke in &self.strokes {
+            let element = match *stroke {
+                Stroke::Line { from, to } => {
+                    let ((x1, y1), (x2, y2)) = (at(from), at(to));
+                    format!(r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke-width="{:.1}"/>"#, x1, y1, x2, y2, width)
+                }
+                Stroke::Arc { from, to, bulge } => {
+                    let ((x1, y1), (x2, y2)) = (at(from), at(to));
+                    let (cx, cy) = arc_control((x1, y1), (x2, y2), bulge);
+                    format!(r#"<path d="M {:.1} {:.1} Q {:.1} {:.1} {:.1} {:.1}" stroke-width="{:.1}"/>"#, x1, y1, cx, cy, x2, y2, width)
+                }
+                Stroke::Loop { at: p } => {
+                    let (cx, cy) = at(p);
+                    format!(r#"<circle cx="{:.1}" cy="{:.1}" r="{:.1}" stroke-width="{:.1}"/>"#, cx, cy, LOOP_RADIUS * scale, width)
+                }
+                Stroke::Dot { at: p } => {
+                    let (cx, cy) = at(p);
+                    format!(r#"<circle cx="{:.1}" cy="{:.1}" r="{:.1}" fill="black" stroke="none"/>"#, cx, cy, DOT_RADIUS * scale)
+                }
+            };
+            svg.push_str(&element);
+            svg.push('\n');
+        }
+        svg
+    }
+
+    // The glyph on its own, as a complete SVG document.
+    pub fn to_svg(&self) -> String {
+        svg_document(GLYPH_SIZE, GLYPH_SIZE, &self.svg_elements(0.0, 0.0, 1.0))
+    }
+}
+
+// The control point of an arc's quadratic curve: off the chord's midpoint, half its length
+// to the side `bulge` picks.
+pub fn arc_control((x1, y1): (f64, f64), (x2, y2): (f64, f64), bulge: i8) -> (f64, f64) {
+    let bulge = f64::from(bulge);
+    ((x1 + x2) / 2.0 - (y2 - y1) / 2.0 * bulge, (y1 + y2) / 2.0 + (x2 - x1) / 2.0 * bulge)
+}
+
+// Wraps elements in an SVG document whose strokes are black, round-ended and unfilled.
+pub fn svg_document(width: f64, height: f64, body: &str) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n\
+         <g fill=\"none\" stroke=\"black\" stroke-linecap=\"round\" stroke-linejoin=\"round\">\n{body}</g>\n</svg>\n",
+        w = width, h = height, body = body
+    )
+}
+
+impl MarkPosition {
+    pub fn label(self) -> &'static str {
+        match self {
+            MarkPosition::Above => "above",
+            MarkPosition::Below => "below",
+            MarkPosition::After => "after",
+        }
+    }
+
+    // Where a mark glyph goes, scaled by MARK_SCALE, relative to a full-size glyph at the origin.
+    pub fn offset(self) -> (f64, f64) {
+        match self {
+            MarkPosition::Above => (GLYPH_SIZE * 0.3, -GLYPH_SIZE * 0.35),
+            MarkPosition::Below => (GLYPH_SIZE * 0.3, GLYPH_SIZE * 0.95),
+            MarkPosition::After => (GLYPH_SIZE * 0.9, GLYPH_SIZE * 0.3),
+        }
+    }
+}
+
+pub const MARK_SCALE: f64 = 0.4;
+
+impl SignKind {
+    pub fn label(self) -> String {
+        match self {
+            SignKind::Consonant => "consonant".to_string(),
+            SignKind::Vowel => "vowel".to_string(),
+            SignKind::Syllable => "syllable".to_string(),
+            SignKind::VowelMark(p) => format!("vowel mark ({})", p.label()),
+            SignKind::Virama(p) => format!("vowel killer ({})", p.label()),
+        }
+    }
+
+    pub fn mark_position(self) -> Option<MarkPosition> {
+        match self {
+            SignKind::VowelMark(p) | SignKind::Virama(p) => Some(p),
+            _ => None,
+        }
+    }
+}
+
+impl Sign {
+    // How the sign is read, e.g. "k" for an abugida consonant becomes "ka".
+    pub fn reading(&self, inherent_vowel: Option<&str>) -> String {
+        match self.kind {
+            SignKind::Consonant => format!("{}{}", self.value, inherent_vowel.unwrap_or("")),
+            SignKind::VowelMark(_) => format!("-{}", self.value),
+            SignKind::Virama(_) => "(no vowel)".to_string(),
+            SignKind::Vowel | SignKind::Syllable => self.value.clone(),
+        }
+    }
+
+    // A file name for the sign's glyph, unique within its writing system.
+    pub fn file_name(&self, index: usize) -> String {
+        let value: String = self.value.chars().map(|c| if c.is_alphanumeric() { c } else { '_' }).collect();
+        let value = match self.kind {
+            SignKind::VowelMark(_) => format!("mark_{}", value),
+            SignKind::Virama(_) => "virama".to_string(),
+            _ => value,
+        };
+        format!("{:03}_{}.svg", index, value)
+    }
+}
+
+const CHART_COLUMNS: usize = 8;
+const CELL_WIDTH: f64 = 120.0;
+const CELL_HEIGHT: f64 = 170.0;
+
+impl WritingSystem {
+    fn inherent(&self) -> Option<&str> {
+        self.inherent_vowel.as_deref().filter(|_| self.kind == ScriptKind::Abugida)
+    }
+
+    // Every sign in a grid, each labelled with how it is read. Marks are shown on a dashed
+    // placeholder circle standing in for the consonant they attach to.
+    pub fn sign_chart_svg(&self) -> String {
+        let rows = self.signs.len().div_ceil(CHART_COLUMNS);
+        let mut body = String::new();
+        for (i, sign) in self.signs.iter().enumerate() {
+            let (x, y) = ((i % CHART_COLUMNS) as f64 * CELL_WIDTH + 10.0, (i / CHART_COLUMNS) as f64 * CELL_HEIGHT + 10.0);
+            body.push_str(&format!(r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" stroke="lightgray" stroke-width="1"/>"#, x, y, CELL_WIDTH - 10.0, CELL_HEIGHT - 10.0));
+            body.push('\n');
+            let (gx, gy) = (x + 5.0, y + 30.0);
+            match sign.kind.mark_position() {
+                Some(position) => {
+                    let scale = 0.8;
+                    body.push_str(&format!(r#"<circle cx="{:.1}" cy="{:.1}" r="{:.1}" stroke="gray" stroke-width="2" stroke-dasharray="4 4"/>"#, gx + GLYPH_SIZE * scale / 2.0, gy + GLYPH_SIZE * scale / 2.0, GLYPH_SIZE * scale * 0.3));
+                    body.push('\n');
+                    let (ox, oy) = position.offset();
+                    body.push_str(&sign.glyph.svg_elements(gx + ox * scale, gy + oy * scale, MARK_SCALE * scale));
+                }
+                None => body.push_str(&sign.glyph.svg_elements(gx + 5.0, gy, 0.9)),
+            }
+            body.push_str(&format!(r#"<text x="{:.1}" y="{:.1}" font-family="sans-serif" font-size="16" text-anchor="middle" fill="black" stroke="none">{}</text>"#, x + (CELL_WIDTH - 10.0) / 2.0, y + CELL_HEIGHT - 20.0, xml_escape(&sign.reading(self.inherent()))));
+            body.push('\n');
+        }
+        svg_document(CHART_COLUMNS.min(self.signs.len().max(1)) as f64 * CELL_WIDTH + 10.0, rows as f64 * CELL_HEIGHT + 10.0, &body)
+    }
+
+    // A CSV table mapping each sign to its reading and glyph file.
+    pub fn mapping_table(&self) -> String {
+        let mut table = String::from("index,value,reading,kind,strokes,file\n");
+        for (i, sign) in self.signs.iter().enumerate() {
+            table.push_str(&format!("{},{},{},{},{},{}\n", i, sign.value, sign.reading(self.inherent()), sign.kind.label(), sign.glyph.strokes.len(), sign.file_name(i)));
+        }
+        table
+    }
+}
+
+pub fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{generator, inventory};
+    use serde_json::json;
+
+    #[test]
+    fn every_sign_gets_its_own_glyph() {
+        let script = generator(json!({ "script": { "kind": "Syllabary" } })).generate_script(&inventory()).unwrap();
+        let glyphs: HashSet<&Vec<Stroke>> = script.signs.iter().map(|s| &s.glyph.strokes).collect();
+        assert_eq!(script.signs.len(), 3 + 9 * 4);
+        assert_eq!(glyphs.len(), script.signs.len());
+    }
+
+    #[test]
+    fn too_few_shapes_is_an_error_not_a_hang() {
+        // Loops alone can only ever make one ring on each of the nine anchors.
+        let generator = generator(json!({ "script": { "strokes": { "line": 0, "arc": 0, "loop": 1, "dot": 0 } } }));
+        let inventory = inventory();
+        let error = generator.generate_script(&inventory).unwrap_err().to_string();
+        assert!(error.contains("only 9 distinct glyphs"), "{}", error);
+        assert!(generator.validate(&inventory).iter().any(|issue| issue.contains("only 9 distinct glyphs")));
+    }
+}
@@ -0,0 +1,242 @@
+use crate::{feature_distance, Lexicon, PhoneticInventory};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// A regular sound change, "from > to / environment": every occurrence of the phoneme `from`
+// in the right environment becomes `to`, in every word at once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SoundChange {
+    pub from: String, // Graphemes from the inventory.
+    pub to: String,
+    #[serde(default)]
+    pub environment: SoundEnvironment,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SoundEnvironment {
+    #[default]
+    Anywhere,
+    WordInitial,
+    WordFinal,
+    Intervocalic, // Between two vowels.
+}
+
+// Why a word's form changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FormChange {
+    Sound(SoundChange),
+    Replaced,  // A dialect coined a new word for the meaning in place of the old one.
+    Restemmed, // Rebuilt on the new form of the word it was derived from.
+}
+
+impl FormChange {
+    pub fn describe(&self) -> String {
+        match self {
+            FormChange::Sound(change) => change.describe(),
+            FormChange::Replaced => "replaced".to_string(),
+            FormChange::Restemmed => "new stem".to_string(),
+        }
+    }
+}
+
+impl SoundChange {
+    // A plausible change for this inventory: a random phoneme shifts to one of the phonemes
+    // of its class that differ from it in the fewest features.
+    pub fn random(inventory: &PhoneticInventory) -> Option<Self> {
+        let mut rng = rand::rng();
+        let phonemes: Vec<_> = inventory.phonemes().collect();
+        let from = phonemes.choose(&mut rng)?;
+
+        let candidates: Vec<_> = phonemes.iter()
+            .filter(|p| p.is_vowel() == from.is_vowel() && p.grapheme() != from.grapheme())
+            .map(|p| (p, feature_distance(&p.features(), &from.features())))
+            .collect();
+        let nearest = candidates.iter().map(|(_, d)| *d).min()?;
+        let closest: Vec<_> = candidates.iter().filter(|(_, d)| *d == nearest).collect();
+        let (to, _) = closest.choose(&mut rng)?;
+
+        // Consonants weaken between vowels far more often than vowels do anything there.
+        let environments: &[(SoundEnvironment, f64)] = if from.is_vowel() {
+            &[(SoundEnvironment::Anywhere, 0.6), (SoundEnvironment::WordFinal, 0.3), (SoundEnvironment::WordInitial, 0.1)]
+        } else {
+            &[(SoundEnvironment::Anywhere, 0.4), (SoundEnvironment::Intervocalic, 0.25), (SoundEnvironment::WordFinal, 0.2), (SoundEnvironment::WordInitial, 0.15)]
+        };
+        let (environment, _) = environments.choose_weighted(&mut rng, |(_, w)| *w).ok()?;
+
+        Some(Self {
+            from: from.grapheme().to_string(),
+            to: to.grapheme().to_string(),
+            environment: *environment,
+        })
+    }
+
+    // Applies the change to one form; each word of an idiom counts as its own word. Hyphens
+    // and other characters outside the inventory are kept but are not phonemes.
+    pub fn apply(&self, inventory: &PhoneticInventory, form: &str) -> String {
+        form.split(' ')
+            .map(|word| self.apply_to_word(inventory, word))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    // Applies the change to an affix such as "-ul" or "az-". The hyphen is where the stem
+    // attaches, so that edge is not a word edge.
+    pub fn apply_to_affix(&self, inventory: &PhoneticInventory, affix: &str) -> String {
+        let blocked = match self.environment {
+            SoundEnvironment::WordInitial => affix.starts_with('-'),
+            SoundEnvironment::WordFinal => affix.ends_with('-'),
+            SoundEnvironment::Anywhere | SoundEnvironment::Intervocalic => false,
+        };
+        if blocked { affix.to_string() } else { self.apply(inventory, affix) }
+    }
+
+    fn apply_to_word(&self, inventory: &PhoneticInventory, word: &str) -> String {
+        let tokens = inventory.tokenize(word);
+        let phoneme_positions: Vec<usize> = tokens.iter().enumerate()
+            .filter(|(_, (_, p))| p.is_some())
+            .map(|(i, _)| i)
+            .collect();
+        let is_vowel_at = |k: Option<&usize>| k.and_then(|&i| tokens[i].1).is_some_and(|p| p.is_vowel());
+
+        let mut result = String::new();
+        let mut k = 0;
+        for (text, phoneme) in &tokens {
+            let Some(phoneme) = phoneme else {
+                result.push_str(text);
+                continue;
+            };
+            let matches = phoneme.grapheme() == self.from && match self.environment {
+                SoundEnvironment::Anywhere => true,
+                SoundEnvironment::WordInitial => k == 0,
+                SoundEnvironment::WordFinal => k + 1 == phoneme_positions.len(),
+                SoundEnvironment::Intervocalic => {
+                    k > 0 && is_vowel_at(phoneme_positions.get(k - 1)) && is_vowel_at(phoneme_positions.get(k + 1))
+                }
+            };
+            result.push_str(if matches { &self.to } else { text });
+            k += 1;
+        }
+        result
+    }
+
+    // Standard notation, e.g. "k > g / V_V".
+    pub fn describe(&self) -> String {
+        let environment = match self.environment {
+            SoundEnvironment::Anywhere => "",
+            SoundEnvironment::WordInitial => " / #_",
+            SoundEnvironment::WordFinal => " / _#",
+            SoundEnvironment::Intervocalic => " / V_V",
+        };
+        format!("{} > {}{}", self.from, self.to, environment)
+    }
+}
+
+impl Lexicon {
+    // Runs a sound change over every word and the affixes its words were built with. Each
+    // altered word records the change and its old form. Returns how many forms it altered.
+    // An affix is read back off its changed word, so the two always agree.
+    pub fn apply_sound_change(&mut self, inventory: &PhoneticInventory, change: &SoundChange) -> usize {
+        let mut changed = 0;
+        for lexeme in self.graph.values_mut() {
+            let form = change.apply(inventory, &lexeme.form);
+            if let Some(affix) = &mut lexeme.affix_applied {
+                *affix = affix_in(&form, affix).unwrap_or_else(|| change.apply_to_affix(inventory, affix));
+            }
+            if form != lexeme.form {
+                let old_form = std::mem::replace(&mut lexeme.form, form);
+                lexeme.form_changes.push((FormChange::Sound(change.clone()), old_form));
+                changed += 1;
+            }
+        }
+        changed
+    }
+}
+
+// The affix as it stands at the edge of `form`, a word built with it: from the last hyphen on
+// for a suffix such as "-ul", up to the first for a prefix such as "az-". `None` for an
+// affix written without a hyphen, which leaves no mark in the word.
+fn affix_in(form: &str, affix: &str) -> Option<String> {
+    if affix.starts_with('-') {
+        form.rfind('-').map(|i| form[i..].to_string())
+    } else if affix.ends_with('-') {
+        form.find('-').map(|i| form[..=i].to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{inventory, root};
+
+    fn change(from: &str, to: &str, environment: SoundEnvironment) -> SoundChange {
+        SoundChange { from: from.to_string(), to: to.to_string(), environment }
+    }
+
+    #[test]
+    fn anywhere_changes_every_occurrence_but_not_longer_graphemes() {
+        let inventory = inventory();
+        let k_to_g = change("k", "g", SoundEnvironment::Anywhere);
+        assert_eq!(k_to_g.apply(&inventory, "kaka"), "gaga");
+        assert_eq!(k_to_g.apply(&inventory, "khak"), "khag");
+    }
+
+    #[test]
+    fn word_initial_and_final_look_at_each_word_of_an_idiom() {
+        let inventory = inventory();
+        let initial = change("k", "g", SoundEnvironment::WordInitial);
+        assert_eq!(initial.apply(&inventory, "kaka koda"), "gaka goda");
+        assert_eq!(initial.apply(&inventory, "az-kad"), "az-kad");
+
+        let final_o = change("o", "u", SoundEnvironment::WordFinal);
+        assert_eq!(final_o.apply(&inventory, "kodo dako"), "kodu daku");
+        assert_eq!(final_o.apply(&inventory, "kod"), "kod");
+    }
+
+    #[test]
+    fn intervocalic_needs_a_vowel_on_both_sides() {
+        let inventory = inventory();
+        let k_to_g = change("k", "g", SoundEnvironment::Intervocalic);
+        assert_eq!(k_to_g.apply(&inventory, "akak"), "agak");
+        assert_eq!(k_to_g.apply(&inventory, "kadka"), "kadka");
+        // Hyphens are not phonemes, so the vowels either side of one still count.
+        assert_eq!(k_to_g.apply(&inventory, "a-ka"), "a-ga");
+    }
+
+    #[test]
+    fn affix_edges_next_to_the_stem_are_not_word_edges() {
+        let inventory = inventory();
+        let initial = change("k", "g", SoundEnvironment::WordInitial);
+        assert_eq!(initial.apply_to_affix(&inventory, "-ka"), "-ka");
+        assert_eq!(initial.apply_to_affix(&inventory, "ka-"), "ga-");
+
+        let final_o = change("o", "u", SoundEnvironment::WordFinal);
+        assert_eq!(final_o.apply_to_affix(&inventory, "-do"), "-du");
+        assert_eq!(final_o.apply_to_affix(&inventory, "do-"), "do-");
+    }
+
+    #[test]
+    fn lexicon_keeps_each_old_form_and_changes_affixes() {
+        let inventory = inventory();
+        let mut lexicon = Lexicon::new();
+        let mut lexeme = root("kado-ko", "noun", "stone", &[]);
+        lexeme.affix_applied = Some("-ko".to_string());
+        let id = lexeme.id;
+        lexicon.add_lexeme(lexeme);
+
+        let first = change("o", "u", SoundEnvironment::WordFinal);
+        let second = change("k", "g", SoundEnvironment::Intervocalic);
+        assert_eq!(lexicon.apply_sound_change(&inventory, &first), 1);
+        assert_eq!(lexicon.apply_sound_change(&inventory, &second), 1);
+        // A change that finds nothing leaves no trace.
+        assert_eq!(lexicon.apply_sound_change(&inventory, &change("d", "g", SoundEnvironment::WordInitial)), 0);
+
+        let lexeme = &lexicon.graph[&id];
+        assert_eq!(lexeme.form, "kado-gu");
+        // The k of the suffix is between vowels in the word, though not in the bare affix.
+        assert_eq!(lexeme.affix_applied.as_deref(), Some("-gu"));
+        assert!(lexeme.form.ends_with(lexeme.affix_applied.as_deref().unwrap()));
+        assert_eq!(lexeme.form_changes, vec![(FormChange::Sound(first), "kado-ko".to_string()), (FormChange::Sound(second), "kado-ku".to_string())]);
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{Phoneme, PhoneticInventory, WordGenerator};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use serde::Deserialize;
+use std::f64::consts::PI;
+
+// A small formant synthesiser for placeholder voice lines. Each phoneme becomes one or more
+// segments (a vowel, a stop's closure and burst, a stretch of fricative noise) whose formant
+// targets come from its features; a glottal pulse train and a noise source are run through
+// three resonators, gliding from one segment's targets to the next.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpeechSettings {
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: u32,
+    #[serde(default = "default_pitch")]
+    pub pitch: f64,   // Base voice pitch in Hz.
+    #[serde(default = "default_rate")]
+    pub rate: f64,    // Speaking speed; 2.0 is twice as fast.
+    #[serde(default)]
+    pub seed: u64,    // For the noise source, so the same text always sounds the same.
+}
+
+fn default_sample_rate() -> u32 { 22050 }
+fn default_pitch() -> f64 { 110.0 }
+fn default_rate() -> f64 { 1.0 }
+
+impl Default for SpeechSettings {
+    fn default() -> Self {
+        Self { sample_rate: default_sample_rate(), pitch: default_pitch(), rate: default_rate(), seed: 0 }
+    }
+}
+
+// Mono audio, samples between -1.0 and 1.0.
+#[derive(Debug, Clone)]
+pub struct Speech {
+    pub sample_rate: u32,
+    pub samples: Vec<f32>,
+}
+
+impl Speech {
+    pub fn duration(&self) -> f64 {
+        self.samples.len() as f64 / self.sample_rate as f64
+    }
+
+    // A 16-bit PCM WAV file.
+    pub fn to_wav(&self) -> Vec<u8> {
+        let data_len = (self.samples.len() * 2) as u32;
+        let mut wav = Vec::with_capacity(44 + data_len as usize);
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // Mono
+        wav.extend_from_slice(&self.sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(self.sample_rate * 2).to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        for sample in &self.samples {
+            wav.extend_from_slice(&((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes());
+        }
+        wav
+    }
+}
+
+// One stretch of sound with steady targets; the synthesiser glides between neighbours.
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    duration: f64,         // Seconds.
+    formants: [f64; 3],    // F1 - F3 in Hz.
+    voice: f64,            // Amplitude of the glottal source.
+    noise: f64,            // Amplitude of the noise source.
+    noise_centre: f64,     // Where fricative and burst noise is loudest; 0.0 sends it through the formants.
+    pitch: (f64, f64),     // Pitch multiplier at the start and end, for stress, tone and intonation.
+}
+
+const WORD_PAUSE: f64 = 0.12;
+const EDGE_SILENCE: f64 = 0.1;
+const FORMANT_BANDWIDTHS: [f64; 3] = [80.0, 100.0, 140.0];
+const GLIDE_TIME: f64 = 0.03;
+const NOISE_LEVEL: f64 = 0.1; // Noise against the glottal source, which is much quieter per sample.
+
+impl WordGenerator {
+    // Speaks a form or a sentence. Hyphens are ignored and letters outside the inventory
+    // are skipped. The stressed syllable of each word is longer, louder and higher, vowels with
+    // a tone feature ("high_tone", "low_tone", "rising", "falling") carry that pitch, and the
+    // voice falls gently over the sentence.
+    pub fn synthesize(&self, inventory: &PhoneticInventory, text: &str) -> Speech {
+        let settings = &self.speech;
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let mut segments = vec![silence(EDGE_SILENCE)];
+        for (w, word) in words.iter().enumerate() {
+            let phonemes = inventory.segment(&word.to_lowercase());
+            let vowels = phonemes.iter().filter(|p| p.is_vowel()).count();
+            let stressed = self.pronunciation.stress.stressed_syllable(vowels);
+            let declination = 1.1 - 0.2 * w as f64 / words.len().max(1) as f64;
+            let mut vowel_index = 0;
+            for (i, phoneme) in phonemes.iter().enumerate() {
+                let next_vowel = phonemes[i + 1..].iter().find(|p| p.is_vowel()).copied();
+                let mut sounds = if phoneme.is_vowel() {
+                    let stress = vowels > 1 && vowel_index == stressed;
+                    vowel_index += 1;
+                    vec![vowel_segment(phoneme, stress)]
+                } else {
+                    consonant_segments(phoneme, next_vowel)
+                };
+                for segment in &mut sounds {
+                    segment.duration /= settings.rate.max(0.1);
+                    segment.pitch.0 *= declination;
+                    segment.pitch.1 *= declination;
+                }
+                segments.extend(sounds);
+            }
+            segments.push(silence(if w + 1 < words.len() { WORD_PAUSE } else { EDGE_SILENCE }));
+        }
+        segments.push(silence(EDGE_SILENCE));
+        Speech { sample_rate: settings.sample_rate, samples: render(&segments, settings) }
+    }
+}
+
+fn silence(duration: f64) -> Segment {
+    Segment { duration, formants: [500.0, 1500.0, 2500.0], voice: 0.0, noise: 0.0, noise_centre: 0.0, pitch: (1.0, 1.0) }
+}
+
+// Vowel formants from height, backness and rounding.
+fn vowel_formants(phoneme: &Phoneme) -> [f64; 3] {
+    let has = |f: &str| phoneme.has_feature(f);
+    let f1 = if has("high") { 300.0 } else if has("low") { 750.0 } else { 500.0 };
+    let mut f2 = if has("front") { 2200.0 } else if has("back") { 900.0 } else { 1400.0 };
+    let mut f3 = 2600.0;
+    if has("rounded") {
+        f2 -= 150.0;
+        f3 -= 200.0;
+    }
+    [f1, f2, f3]
+}
+
+fn vowel_segment(phoneme: &Phoneme, stressed: bool) -> Segment {
+    let has = |f: &str| phoneme.has_feature(f);
+    let level = if has("high_tone") { 1.25 } else if has("low_tone") { 0.8 } else { 1.0 };
+    let mut pitch = if has("rising") { (0.85, 1.2) } else if has("falling") { (1.2, 0.85) } else { (level, level) };
+    let (mut duration, mut voice) = (0.12, 0.8);
+    if stressed {
+        duration *= 1.5;
+        voice *= 1.3;
+        pitch = (pitch.0 * 1.15, pitch.1 * 1.15);
+    }
+    Segment { duration, formants: vowel_formants(phoneme), voice, noise: 0.0, noise_centre: 0.0, pitch }
+}
+
+// Where the formants point during a consonant (its "locus"), and where its noise is loudest.
+fn place(phoneme: &Phoneme) -> ([f64; 3], f64) {
+    let has = |f: &str| phoneme.has_feature(f);
+    if has("labial") {
+        ([300.0, 900.0, 2300.0], 1500.0)
+    } else if has("dental") {
+        ([350.0, 1500.0, 2600.0], 5500.0)
+    } else if has("alveolar") {
+        ([350.0, 1700.0, 2700.0], 4500.0)
+    } else if has("postalveolar") || has("palatal") {
+        ([300.0, 2000.0, 2800.0], 3000.0)
+    } else if has("velar") {
+        ([300.0, 2200.0, 2500.0], 2000.0)
+    } else if has("uvular") {
+        ([400.0, 1500.0, 2500.0], 1400.0)
+    } else {
+        ([500.0, 1500.0, 2500.0], 0.0)
+    }
+}
+
+// A consonant's segments by manner. Stops are a closure then a burst (and a puff of aspiration
+// when voiceless); fricatives are filtered noise; nasals, liquids and glides are quiet vowels.
+fn consonant_segments(phoneme: &Phoneme, next_vowel: Option<&Phoneme>) -> Vec<Segment> {
+    let has = |f: &str| phoneme.has_feature(f);
+    let voiced = has("voiced");
+    let (locus, centre) = place(phoneme);
+    let voice_bar = if voiced { 0.15 } else { 0.0 };
+    let segment = |duration: f64, formants: [f64; 3], voice: f64, noise: f64, noise_centre: f64| {
+        Segment { duration, formants, voice, noise, noise_centre, pitch: (1.0, 1.0) }
+    };
+    let aspiration = next_vowel.map_or(locus, vowel_formants);
+
+    if has("stop") || has("affricate") {
+        let mut segments = vec![
+            segment(0.06, locus, voice_bar, 0.0, 0.0),
+            segment(0.015, locus, voice_bar, 0.6, centre),
+        ];
+        if has("affricate") {
+            segments.push(segment(0.07, locus, voice_bar * 2.0, 0.45, centre));
+        } else if !voiced {
+            segments.push(segment(0.035, aspiration, 0.0, 0.25, 0.0));
+        }
+        segments
+    } else if has("fricative") {
+        let noise = if has("dental") || has("labial") { 0.2 } else { 0.4 };
+        if has("glottal") {
+            vec![segment(0.08, aspiration, 0.0, 0.3, 0.0)]
+        } else {
+            vec![segment(0.1, locus, voice_bar * 2.0, noise, centre)]
+        }
+    } else if has("nasal") {
+        let mut formants = locus;
+        formants[0] = 250.0;
+        vec![segment(0.07, formants, 0.35, 0.0, 0.0)]
+    } else if has("liquid") {
+        let formants = if has("lateral") { [350.0, 1100.0, 2700.0] } else { [400.0, 1200.0, 1700.0] };
+        vec![segment(0.07, formants, 0.45, 0.0, 0.0)]
+    } else if has("glide") {
+        let formants = if has("labial") { [300.0, 750.0, 2300.0] } else { [280.0, 2250.0, 2900.0] };
+        vec![segment(0.05, formants, 0.5, 0.0, 0.0)]
+    } else {
+        // No features to go on: a short, neutral burst of noise.
+        vec![segment(0.06, locus, voice_bar, 0.3, 0.0)]
+    }
+}
+
+// A two-pole resonator, as in the Klatt synthesiser.
+#[derive(Default)]
+struct Resonator {
+    y1: f64,
+    y2: f64,
+}
+
+impl Resonator {
+    fn process(&mut self, x: f64, frequency: f64, bandwidth: f64, sample_rate: f64) -> f64 {
+        let t = 1.0 / sample_rate;
+        let c = -(-2.0 * PI * bandwidth * t).exp();
+        let b = 2.0 * (-PI * bandwidth * t).exp() * (2.0 * PI * frequency * t).cos();
+        let a = 1.0 - b - c;
+        let y = a * x + b * self.y1 + c * self.y2;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+fn render(segments: &[Segment], settings: &SpeechSettings) -> Vec<f32> {
+    let rate = settings.sample_rate as f64;
+    let nyquist_limit = rate * 0.45;
+    let mut rng = StdRng::seed_from_u64(settings.seed);
+    let mut formant_filters: [Resonator; 3] = Default::default();
+    let mut noise_filter = Resonator::default();
+    let (mut phase, mut last_pulse) = (0.0, 0.0);
+    let (mut voice, mut noise) = (0.0, 0.0);
+    let smoothing = 1.0 - (-1.0 / (0.004 * rate)).exp(); // About 4ms, to avoid clicks.
+    let mut samples = Vec::new();
+
+    for (index, segment) in segments.iter().enumerate() {
+        let next = segments.get(index + 1).unwrap_or(segment);
+        let length = (segment.duration * rate).round() as usize;
+        for n in 0..length {
+            let progress = n as f64 / length.max(1) as f64;
+            // Glide into the next segment's targets over the last GLIDE_TIME seconds.
+            let remaining = (length - n) as f64 / rate;
+            let glide = if remaining < GLIDE_TIME { 1.0 - remaining / GLIDE_TIME } else { 0.0 };
+            let formant = |i: usize| (segment.formants[i] + (next.formants[i] - segment.formants[i]) * glide).min(nyquist_limit);
+            let pitch = settings.pitch * (segment.pitch.0 + (segment.pitch.1 - segment.pitch.0) * progress);
+
+            voice += (segment.voice - voice) * smoothing;
+            noise += (segment.noise - noise) * smoothing;
+
+            // Rosenberg glottal pulse, differentiated for lip radiation.
+            phase = (phase + pitch / rate) % 1.0;
+            let pulse = if phase < 0.4 {
+                0.5 * (1.0 - (PI * phase / 0.4).cos())
+            } else if phase < 0.56 {
+                (PI * (phase - 0.4) / 0.32).cos()
+            } else {
+                0.0
+            };
+            let source = (pulse - last_pulse) * 8.0 * voice;
+            last_pulse = pulse;
+            let hiss = (rng.random::<f64>() * 2.0 - 1.0) * noise * NOISE_LEVEL;
+
+            let mut voiced = source;
+            let mut aspirated = 0.0;
+            if segment.noise_centre > 0.0 {
+                aspirated = noise_filter.process(hiss, segment.noise_centre.min(nyquist_limit), 1200.0, rate);
+            } else {
+                voiced += hiss;
+            }
+            for (i, filter) in formant_filters.iter_mut().enumerate() {
+                voiced = filter.process(voiced, formant(i), FORMANT_BANDWIDTHS[i], rate);
+            }
+            samples.push(voiced + aspirated);
+        }
+    }
+
+    let peak = samples.iter().fold(0.0f64, |peak, s| peak.max(s.abs()));
+    let gain = if peak > 0.0 { 0.9 / peak } else { 0.0 };
+    samples.into_iter().map(|s| (s * gain) as f32).collect()
+}
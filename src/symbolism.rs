@@ -0,0 +1,77 @@
+use crate::{Meaning, Phoneme};
+use serde::Deserialize;
+
+// Optional sound-symbolic biases for root generation. Writing `"sound_symbolism": {}` in the
+// config switches on the standard associations; `biases` replaces them entirely.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SoundSymbolism {
+    #[serde(default = "default_strength")]
+    pub strength: f64, // How strongly a fully matching phoneme is favoured (0.0 = no effect).
+    #[serde(default = "default_biases")]
+    pub biases: Vec<SymbolicBias>,
+    #[serde(default = "default_reduplicate_tags")]
+    pub reduplicate_tags: Vec<String>, // Meanings with any of these tags lean toward reduplicated roots.
+    #[serde(default = "default_reduplication_chance")]
+    pub reduplication_chance: f64,
+}
+
+// "Meanings tagged with any of `tags` prefer phonemes with the `favour` features."
+#[derive(Debug, Clone, Deserialize)]
+pub struct SymbolicBias {
+    pub tags: Vec<String>,
+    pub favour: Vec<String>,
+}
+
+fn default_strength() -> f64 { 4.0 }
+fn default_reduplication_chance() -> f64 { 0.6 }
+fn default_reduplicate_tags() -> Vec<String> { vec!["onomatopoeia".to_string()] }
+
+fn default_biases() -> Vec<SymbolicBias> {
+    let bias = |tags: &[&str], favour: &[&str]| SymbolicBias {
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        favour: favour.iter().map(|f| f.to_string()).collect(),
+    };
+    vec![
+        bias(&["small", "bright", "near"], &["high", "front"]),
+        bias(&["big", "heavy", "dark", "far"], &["back", "low"]),
+    ]
+}
+
+impl Default for SoundSymbolism {
+    fn default() -> Self {
+        Self {
+            strength: default_strength(),
+            biases: default_biases(),
+            reduplicate_tags: default_reduplicate_tags(),
+            reduplication_chance: default_reduplication_chance(),
+        }
+    }
+}
+
+impl SoundSymbolism {
+    // The phonetic features favoured for a meaning, gathered from every bias its tags trigger.
+    pub fn favoured_features(&self, meaning: &Meaning) -> Vec<&str> {
+        let mut features: Vec<&str> = Vec::new();
+        for bias in self.biases.iter().filter(|b| b.tags.iter().any(|t| meaning.tags.contains(t))) {
+            for feature in &bias.favour {
+                if !features.contains(&feature.as_str()) {
+                    features.push(feature);
+                }
+            }
+        }
+        features
+    }
+
+    // Relative weight of a phoneme: 1.0, plus `strength` scaled by how many favoured features it has.
+    pub fn weight(&self, phoneme: &Phoneme, favoured: &[&str]) -> f64 {
+        if favoured.is_empty() {
+            return 1.0;
+        }
+        let matches = favoured.iter().filter(|f| phoneme.has_feature(f)).count();
+        1.0 + self.strength.max(0.0) * matches as f64 / favoured.len() as f64
+    }
+
+    pub fn wants_reduplication(&self, meaning: &Meaning) -> bool {
+        self.reduplicate_tags.iter().any(|t| meaning.tags.contains(t))
+    }
+}
@@ -0,0 +1,83 @@
+// Small fixtures shared by the unit tests.
+use crate::script::{Glyph, Sign, SignKind, Stroke};
+use crate::{LanguageConfig, Lexeme, Origin, Phoneme, PhoneticInventory, ScriptKind, Sense, SenseSource, WordGenerator, WritingSystem};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Single letters plus the digraphs "kh", "th" and "gr", so tokenizing has to pick the longest.
+const PHONEMES: &str = r#"[
+    { "grapheme": "k", "sound_type": "Consonant" },
+    { "grapheme": "kh", "sound_type": "Consonant" },
+    { "grapheme": "t", "sound_type": "Consonant" },
+    { "grapheme": "th", "sound_type": "Consonant" },
+    { "grapheme": "h", "sound_type": "Consonant" },
+    { "grapheme": "g", "sound_type": "Consonant" },
+    { "grapheme": "gr", "sound_type": "Consonant" },
+    { "grapheme": "r", "sound_type": "Consonant" },
+    { "grapheme": "d", "sound_type": "Consonant" },
+    { "grapheme": "a", "sound_type": "Vowel" },
+    { "grapheme": "o", "sound_type": "Vowel" },
+    { "grapheme": "u", "sound_type": "Vowel" }
+]"#;
+
+pub fn inventory() -> PhoneticInventory {
+    let phonemes: Vec<Phoneme> = serde_json::from_str(PHONEMES).unwrap();
+    PhoneticInventory::new(phonemes)
+}
+
+// A generator over `inventory()` making CV and CVC syllables, with `settings` (a JSON object
+// of top-level config sections) on top.
+pub fn generator(settings: Value) -> WordGenerator {
+    let mut config = json!({
+        "phonemes": serde_json::from_str::<Value>(PHONEMES).unwrap(),
+        "syllable_rules": ["CV", "CVC"],
+        "min_syllables": 1,
+        "max_syllables": 2,
+    });
+    if let (Some(config), Value::Object(settings)) = (config.as_object_mut(), settings) {
+        config.extend(settings);
+    }
+    let config: LanguageConfig = serde_json::from_value(config).unwrap();
+    config.into_generator().1
+}
+
+// A root word with one core sense.
+pub fn root(form: &str, part_of_speech: &str, gloss: &str, tags: &[&str]) -> Lexeme {
+    Lexeme {
+        id: Uuid::new_v4(),
+        form: form.to_string(),
+        part_of_speech: part_of_speech.to_string(),
+        senses: vec![Sense::new(gloss, tags.iter().map(|t| t.to_string()).collect(), SenseSource::Core)],
+        meaning_shifts: Vec::new(),
+        form_changes: Vec::new(),
+        parent_id: None,
+        rule_applied: None,
+        affix_applied: None,
+        origin: Origin::Root,
+        borrowed_from: None,
+    }
+}
+
+// A script whose signs all share one simple glyph; only the values and kinds differ.
+pub fn script(kind: ScriptKind, inherent_vowel: Option<&str>, signs: &[(&str, SignKind)]) -> WritingSystem {
+    let glyph = Glyph { grid: 3, strokes: vec![Stroke::Line { from: (0, 0), to: (2, 2) }, Stroke::Dot { at: (1, 0) }] };
+    WritingSystem {
+        kind,
+        seed: 0,
+        inherent_vowel: inherent_vowel.map(str::to_string),
+        signs: signs.iter()
+            .map(|(value, kind)| Sign { value: value.to_string(), kind: *kind, glyph: glyph.clone() })
+            .collect(),
+    }
+}
+
+// A word derived from `parent` by the rule `rule`, which attached `affix`.
+pub fn derived(parent: &Lexeme, form: &str, gloss: &str, rule: &str, affix: &str) -> Lexeme {
+    Lexeme {
+        parent_id: Some(parent.id),
+        rule_applied: Some(rule.to_string()),
+        affix_applied: Some(affix.to_string()),
+        origin: Origin::Derivation,
+        ..root(form, &parent.part_of_speech, gloss, &[])
+    }
+}
@@ -0,0 +1,243 @@
+use crate::script::{svg_document, xml_escape, MarkPosition, SignKind, GLYPH_SIZE, MARK_SCALE};
+use crate::{Lexeme, PhoneticInventory, ScriptKind, WritingSystem};
+
+// One written unit: a sign (an index into `WritingSystem::signs`) with the abugida mark
+// attached to it, if any, or a letter the script has no sign for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Written {
+    Sign { base: usize, mark: Option<usize> },
+    Unknown(String),
+}
+
+// Running text is drawn at SCALE of a glyph's full size.
+const SCALE: f64 = 0.6;
+const ADVANCE: f64 = GLYPH_SIZE * SCALE;
+const WORD_GAP: f64 = GLYPH_SIZE * SCALE * 0.6;
+const LINE_HEIGHT: f64 = GLYPH_SIZE * SCALE * 1.8;
+const MAX_LINE_WIDTH: f64 = 1200.0;
+const PADDING: f64 = 30.0;
+
+impl WritingSystem {
+    // Writes a form in the script, one list of units per space-separated word. The form is
+    // split into the inventory's graphemes longest first, so "kh" is one sign and not "k"
+    // then "h". Hyphens and other non-letters mark morpheme boundaries and are not written.
+    pub fn transliterate(&self, inventory: &PhoneticInventory, form: &str) -> Vec<Vec<Written>> {
+        form.split_whitespace()
+            .map(|word| self.transliterate_word(inventory, word))
+            .filter(|units| !units.is_empty())
+            .collect()
+    }
+
+    pub fn transliterate_lexeme(&self, inventory: &PhoneticInventory, lexeme: &Lexeme) -> Vec<Vec<Written>> {
+        self.transliterate(inventory, &lexeme.form)
+    }
+
+    fn transliterate_word(&self, inventory: &PhoneticInventory, word: &str) -> Vec<Written> {
+        let tokens: Vec<_> = inventory.tokenize(word).into_iter()
+            .filter(|(text, phoneme)| phoneme.is_some() || text.chars().all(char::is_alphabetic))
+            .collect();
+        let mut units = Vec::new();
+        let mut i = 0;
+        while i < tokens.len() {
+            let (text, phoneme) = tokens[i];
+            let Some(phoneme) = phoneme else {
+                units.push(Written::Unknown(text.to_string()));
+                i += 1;
+                continue;
+            };
+            // The vowel right after a consonant, which abugidas and syllabaries write with it.
+            let next_vowel = tokens.get(i + 1)
+                .and_then(|(_, p)| *p)
+                .filter(|p| !phoneme.is_vowel() && p.is_vowel());
+            let find = |value: &str, kind: fn(SignKind) -> bool| self.signs.iter().position(|s| s.value == value && kind(s.kind));
+            let consonant = |k: SignKind| k == SignKind::Consonant;
+            let vowel = |k: SignKind| k == SignKind::Vowel;
+
+            let (unit, used) = match self.kind {
+                ScriptKind::Alphabet => {
+                    let kind = if phoneme.is_vowel() { vowel } else { consonant };
+                    (find(phoneme.grapheme(), kind).map(|base| Written::Sign { base, mark: None }), 1)
+                }
+                ScriptKind::Abjad => {
+                    if phoneme.is_vowel() {
+                        i += 1;
+                        continue;
+                    }
+                    (find(phoneme.grapheme(), consonant).map(|base| Written::Sign { base, mark: None }), 1)
+                }
+                ScriptKind::Abugida => {
+                    if phoneme.is_vowel() {
+                        (find(phoneme.grapheme(), vowel).map(|base| Written::Sign { base, mark: None }), 1)
+                    } else {
+                        let base = find(phoneme.grapheme(), consonant);
+                        let (mark, used) = match next_vowel {
+                            Some(v) if Some(v.grapheme()) == self.inherent_vowel.as_deref() => (None, 2),
+                            Some(v) => (find(v.grapheme(), |k| matches!(k, SignKind::VowelMark(_))), 2),
+                            None => (self.signs.iter().position(|s| matches!(s.kind, SignKind::Virama(_))), 1),
+                        };
+                        (base.map(|base| Written::Sign { base, mark }), used)
+                    }
+                }
+                ScriptKind::Syllabary => {
+                    let syllable = next_vowel
+                        .and_then(|v| find(&format!("{}{}", phoneme.grapheme(), v.grapheme()), |k| k == SignKind::Syllable));
+                    match syllable {
+                        Some(base) => (Some(Written::Sign { base, mark: None }), 2),
+                        None => {
+                            let kind = if phoneme.is_vowel() { vowel } else { consonant };
+                            (find(phoneme.grapheme(), kind).map(|base| Written::Sign { base, mark: None }), 1)
+                        }
+                    }
+                }
+            };
+            units.push(unit.unwrap_or_else(|| Written::Unknown(text.to_string())));
+            i += used;
+        }
+        units
+    }
+
+    // How each written unit reads, e.g. "ku·tha·k" for an abugida "kuthak".
+    pub fn describe(&self, written: &[Vec<Written>]) -> String {
+        let inherent = self.inherent_vowel.as_deref().filter(|_| self.kind == ScriptKind::Abugida);
+        written.iter()
+            .map(|word| word.iter()
+                .map(|unit| match unit {
+                    Written::Sign { base, mark } => {
+                        match mark {
+                            Some(mark) => format!("{}{}", self.signs[*base].value, self.signs[*mark].value),
+                            None => self.signs[*base].reading(inherent),
+                        }
+                    }
+                    Written::Unknown(text) => format!("?{}", text),
+                })
+                .collect::<Vec<_>>()
+                .join("·"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    // Renders forms (words or whole sentences) as one SVG, left to right, wrapping long lines.
+    // Letters the script cannot write are shown in plain text.
+    pub fn render_svg(&self, inventory: &PhoneticInventory, text: &str) -> String {
+        let written = self.transliterate(inventory, text);
+        let mut body = String::new();
+        let (mut x, mut y) = (PADDING, PADDING + GLYPH_SIZE * SCALE * MARK_SCALE);
+        let mut width: f64 = 0.0;
+        for word in &written {
+            let word_width: f64 = word.iter().map(|unit| self.advance(unit)).sum();
+            if x > PADDING && x + word_width > MAX_LINE_WIDTH {
+                x = PADDING;
+                y += LINE_HEIGHT;
+            }
+            for unit in word {
+                match unit {
+                    Written::Sign { base, mark } => {
+                        body.push_str(&self.signs[*base].glyph.svg_elements(x, y, SCALE));
+                        if let Some(mark) = mark {
+                            let sign = &self.signs[*mark];
+                            let (ox, oy) = sign.kind.mark_position().unwrap_or(MarkPosition::After).offset();
+                            body.push_str(&sign.glyph.svg_elements(x + ox * SCALE, y + oy * SCALE, MARK_SCALE * SCALE));
+                        }
+                    }
+                    Written::Unknown(text) => {
+                        body.push_str(&format!(r#"<text x="{:.1}" y="{:.1}" font-family="serif" font-size="{:.1}" fill="black" stroke="none">{}</text>"#,
+                            x, y + ADVANCE * 0.75, ADVANCE * 0.6, xml_escape(text)));
+                        body.push('\n');
+                    }
+                }
+                x += self.advance(unit);
+            }
+            width = width.max(x);
+            x += WORD_GAP;
+        }
+        let height = y + LINE_HEIGHT;
+        svg_document((width + PADDING).max(2.0 * PADDING), height, &body)
+    }
+
+    // How far a unit moves the pen; a mark placed after its consonant takes extra room.
+    fn advance(&self, unit: &Written) -> f64 {
+        match unit {
+            Written::Sign { mark: Some(mark), .. } if self.signs[*mark].kind.mark_position() == Some(MarkPosition::After) => {
+                ADVANCE + GLYPH_SIZE * SCALE * MARK_SCALE
+            }
+            Written::Sign { .. } => ADVANCE,
+            Written::Unknown(text) => ADVANCE * 0.4 * text.chars().count() as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{inventory, script};
+
+    #[test]
+    fn tokenize_takes_the_longest_grapheme() {
+        let inventory = inventory();
+        let tokens: Vec<&str> = inventory.tokenize("khagrthu-rh").into_iter().map(|(text, _)| text).collect();
+        assert_eq!(tokens, ["kh", "a", "gr", "th", "u", "-", "r", "h"]);
+        let unknown: Vec<&str> = inventory.tokenize("kal").into_iter().filter(|(_, p)| p.is_none()).map(|(text, _)| text).collect();
+        assert_eq!(unknown, ["l"]);
+    }
+
+    #[test]
+    fn alphabet_writes_digraphs_as_one_letter() {
+        let inventory = inventory();
+        let signs: Vec<(&str, SignKind)> = ["a", "u"].iter().map(|v| (*v, SignKind::Vowel))
+            .chain(["k", "kh", "t", "th", "h", "g", "gr", "r"].iter().map(|c| (*c, SignKind::Consonant)))
+            .collect();
+        let alphabet = script(ScriptKind::Alphabet, None, &signs);
+
+        let written = alphabet.transliterate(&inventory, "khagr th-uk");
+        assert_eq!(alphabet.describe(&written), "kh·a·gr th·u·k");
+        assert_eq!(written[0][0], Written::Sign { base: 3, mark: None });
+        // Letters outside the inventory are kept but cannot be written.
+        assert_eq!(alphabet.describe(&alphabet.transliterate(&inventory, "kal")), "k·a·?l");
+    }
+
+    #[test]
+    fn abjad_leaves_out_vowels() {
+        let inventory = inventory();
+        let abjad = script(ScriptKind::Abjad, None, &[("k", SignKind::Consonant), ("th", SignKind::Consonant)]);
+        assert_eq!(abjad.describe(&abjad.transliterate(&inventory, "kuthak")), "k·th·k");
+    }
+
+    #[test]
+    fn abugida_uses_the_inherent_vowel_marks_and_virama() {
+        let inventory = inventory();
+        let abugida = script(ScriptKind::Abugida, Some("a"), &[
+            ("k", SignKind::Consonant),
+            ("th", SignKind::Consonant),
+            ("a", SignKind::Vowel),
+            ("u", SignKind::Vowel),
+            ("u", SignKind::VowelMark(MarkPosition::Above)),
+            ("", SignKind::Virama(MarkPosition::Below)),
+        ]);
+
+        let written = abugida.transliterate(&inventory, "kuthak");
+        assert_eq!(written, vec![vec![
+            Written::Sign { base: 0, mark: Some(4) },
+            Written::Sign { base: 1, mark: None },
+            Written::Sign { base: 0, mark: Some(5) },
+        ]]);
+        assert_eq!(abugida.describe(&written), "ku·tha·k");
+        // A word-initial vowel has nothing to ride on, so it gets its own letter.
+        assert_eq!(abugida.describe(&abugida.transliterate(&inventory, "uk")), "u·k");
+    }
+
+    #[test]
+    fn syllabary_joins_consonant_and_vowel_and_falls_back_to_bare_consonants() {
+        let inventory = inventory();
+        let syllabary = script(ScriptKind::Syllabary, None, &[
+            ("a", SignKind::Vowel),
+            ("u", SignKind::Vowel),
+            ("ku", SignKind::Syllable),
+            ("tha", SignKind::Syllable),
+            ("k", SignKind::Consonant),
+            ("gr", SignKind::Consonant),
+        ]);
+        assert_eq!(syllabary.describe(&syllabary.transliterate(&inventory, "kuthak")), "ku·tha·k");
+        // No "gra" syllable: the bare consonant, then the vowel on its own.
+        assert_eq!(syllabary.describe(&syllabary.transliterate(&inventory, "gra")), "gr·a");
+    }
+}
@@ -0,0 +1,312 @@
+use crate::historical_text::EVENT_FIELDS;
+use crate::naming::NamePart;
+use regex::Regex;
+use crate::{culture, DriftKind, MeaningAssignment, PhoneticInventory, Register, Scheme, ScriptKind, WordGenerator};
+
+impl WordGenerator {
+    // Cross-checks a configuration that parsed fine but contains rules that can never
+    // work as written. Returns one human-readable message per problem found.
+    pub fn validate(&self, inventory: &PhoneticInventory) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        if inventory.vowels.is_empty() {
+            issues.push("The phoneme inventory has no vowels.".to_string());
+        }
+        if inventory.consonants.is_empty() && self.rules.iter().any(|r| r.pattern.contains('C')) {
+            issues.push("Syllable rules use 'C' but the phoneme inventory has no consonants.".to_string());
+        }
+        if self.rules.is_empty() {
+            issues.push("No syllable rules are defined.".to_string());
+        }
+        if self.min_syllables > self.max_syllables {
+            issues.push(format!("min_syllables ({}) is greater than max_syllables ({}).", self.min_syllables, self.max_syllables));
+        }
+
+        if self.lexicon_generation.assignment == MeaningAssignment::Unique && self.concept_count() == 0 {
+            issues.push("Meaning assignment is 'Unique' but there are no concepts to assign.".to_string());
+        }
+
+        if let Some(symbolism) = &self.sound_symbolism {
+            if symbolism.strength < 0.0 {
+                issues.push(format!("sound_symbolism.strength ({}) must not be negative.", symbolism.strength));
+            }
+            if !(0.0..=1.0).contains(&symbolism.reduplication_chance) {
+                issues.push(format!("sound_symbolism.reduplication_chance ({}) is outside 0.0 - 1.0.", symbolism.reduplication_chance));
+            }
+        }
+
+        let culture = self.lexicon_generation.culture.as_ref();
+        if let Some(culture) = culture {
+            for environment in culture.environment.iter().filter(|e| !culture::known_environments().any(|k| k == e.as_str())) {
+                issues.push(format!("Unknown culture environment '{}' (known: {}).", environment, culture::known_environments().collect::<Vec<_>>().join(", ")));
+            }
+            for value in culture.values.iter().filter(|v| !culture::known_values().any(|k| k == v.as_str())) {
+                issues.push(format!("Unknown culture value '{}' (known: {}).", value, culture::known_values().collect::<Vec<_>>().join(", ")));
+            }
+        }
+        let salience = culture.map(|c| c.domain_salience());
+
+        let rules = &self.morphology.derivational_rules;
+        let rule_exists = |name: &String| rules.iter().any(|r| &r.name == name);
+        // A part of speech can reach a rule either from root generation or as another rule's output.
+        let pos_is_reachable = |pos: &String| {
+            self.lexicon_generation.parts_of_speech.contains(pos)
+                || rules.iter().any(|r| &r.output_pos == pos)
+        };
+
+        // Tags can only come from a root's meaning or from a rule that adds them.
+        let known_tags: Vec<&String> = self.lexicon_generation.meanings.values()
+            .flatten()
+            .flat_map(|m| m.tags.iter())
+            .chain(rules.iter().flat_map(|r| r.adds_tags.iter()))
+            .collect();
+
+        for (index, rule) in rules.iter().enumerate() {
+            if rules[..index].iter().any(|r| r.name == rule.name) {
+                issues.push(format!("Rule '{}' is defined more than once.", rule.name));
+            }
+            if !(0.0..=1.0).contains(&rule.productivity) {
+                issues.push(format!("Rule '{}' has productivity {} outside 0.0 - 1.0.", rule.name, rule.productivity));
+            }
+            for pos in &rule.applies_to_pos {
+                if !pos_is_reachable(pos) {
+                    issues.push(format!("Rule '{}' applies to '{}', but no word ever has that part of speech.", rule.name, pos));
+                }
+            }
+
+            if let Some(domain) = &rule.domain
+                && !salience.as_ref().is_some_and(|s| s.is_salient(domain)) {
+                issues.push(format!("Rule '{}' belongs to domain '{}', which the culture does not make salient, so it never fires.", rule.name, domain));
+            }
+
+            let c = &rule.constraints;
+            for name in c.cannot_follow_rules.iter().filter(|n| !rule_exists(n)) {
+                issues.push(format!("Rule '{}' cannot_follow_rules names unknown rule '{}'.", rule.name, name));
+            }
+            for name in c.cannot_be_followed_by.iter().filter(|n| !rule_exists(n)) {
+                issues.push(format!("Rule '{}' cannot_be_followed_by names unknown rule '{}'.", rule.name, name));
+            }
+            for tag in c.required_parent_tags.iter().filter(|t| !known_tags.contains(t)) {
+                issues.push(format!("Rule '{}' requires the tag '{}', but no meaning or rule ever provides it.", rule.name, tag));
+            }
+            for tag in c.required_parent_tags.iter().filter(|t| c.excluded_parent_tags.contains(t)) {
+                issues.push(format!("Rule '{}' both requires and excludes the tag '{}'.", rule.name, tag));
+            }
+            if let (Some(min), Some(max)) = (c.min_parent_syllables, c.max_parent_syllables)
+                && min > max {
+                issues.push(format!("Rule '{}' has min_parent_syllables ({}) greater than max_parent_syllables ({}).", rule.name, min, max));
+            }
+            if let (Some(min), Some(max)) = (c.min_parent_depth, c.max_parent_depth)
+                && min > max {
+                issues.push(format!("Rule '{}' has min_parent_depth ({}) greater than max_parent_depth ({}).", rule.name, min, max));
+            }
+            if let (Some(min), Some(max_depth)) = (c.min_parent_depth, self.morphology.max_depth)
+                && min >= max_depth {
+                issues.push(format!("Rule '{}' needs a parent at depth {} but morphology.max_depth is {}, so it can never fire.", rule.name, min, max_depth));
+            }
+        }
+
+        for metaphor in &self.lexicon_generation.metaphors {
+            if !known_tags.contains(&&metaphor.source_domain) {
+                issues.push(format!("Metaphor '{}' draws on domain '{}', but no meaning or rule carries that tag.", metaphor.name, metaphor.source_domain));
+            }
+            for extension in &metaphor.extensions {
+                if !self.concepts().iter().any(|(_, m)| m.gloss == extension.source) {
+                    issues.push(format!("Metaphor '{}' extends '{}', which is not one of the language's meanings.", metaphor.name, extension.source));
+                }
+            }
+            if !(0.0..=1.0).contains(&metaphor.extension_chance) {
+                issues.push(format!("Metaphor '{}' has extension_chance {} outside 0.0 - 1.0.", metaphor.name, metaphor.extension_chance));
+            }
+        }
+
+        if let Some(drift) = &self.lexicon_generation.drift {
+            for kind in DriftKind::ALL {
+                let rate = drift.rates.of(kind);
+                if !(0.0..=1.0).contains(&rate) {
+                    issues.push(format!("Drift rate for {} is {}, outside 0.0 - 1.0.", kind.label(), rate));
+                }
+            }
+            for shift in &drift.shifts {
+                if !self.concepts().iter().any(|(_, m)| m.gloss == shift.from) {
+                    issues.push(format!("Drift shift '{}' -> '{}' starts from a meaning the language does not have.", shift.from, shift.to));
+                }
+                if shift.kind == DriftKind::Taboo {
+                    issues.push(format!("Drift shift '{}' -> '{}' is a taboo replacement; those pick their own euphemism and cannot be hand-picked.", shift.from, shift.to));
+                }
+            }
+            if drift.rates.taboo > 0.0 && !drift.taboo_tags.iter().any(|t| known_tags.contains(&t)) {
+                issues.push("Drift has a taboo rate but no meaning carries any of its taboo_tags.".to_string());
+            }
+        }
+
+        for change in &self.lexicon_generation.sound_changes {
+            if !inventory.phonemes().any(|p| p.grapheme == change.from) {
+                issues.push(format!("Sound change '{}' changes '{}', which is not in the phoneme inventory.", change.describe(), change.from));
+            }
+        }
+
+        let dialects = &self.dialects;
+        for (name, value) in [("innovation_rate", dialects.innovation_rate), ("sound_change_share", dialects.sound_change_share)] {
+            if !(0.0..=1.0).contains(&value) {
+                issues.push(format!("Dialect {} is {}, outside 0.0 - 1.0.", name, value));
+            }
+        }
+        if dialects.spread_range <= 0.0 {
+            issues.push(format!("Dialect spread_range must be positive, but is {}.", dialects.spread_range));
+        }
+        if dialects.communities.is_empty() {
+            for barrier in &dialects.grid.barriers {
+                if barrier.x >= dialects.grid.width || barrier.y >= dialects.grid.height {
+                    issues.push(format!("Dialect barrier at ({}, {}) lies outside the {}x{} grid.", barrier.x, barrier.y, dialects.grid.width, dialects.grid.height));
+                }
+            }
+        } else if !dialects.grid.barriers.is_empty() {
+            issues.push("Dialect grid barriers are ignored because communities are listed explicitly.".to_string());
+        }
+        if dialects.map.is_some() && !dialects.communities.is_empty() {
+            issues.push("Dialect communities are ignored because a map file is given.".to_string());
+        }
+        for (terrain, cost) in &dialects.terrain_costs {
+            if *cost < 0.0 {
+                issues.push(format!("Terrain cost of '{}' is negative ({}).", terrain, cost));
+            }
+        }
+        match dialects.community_graph() {
+            Ok(graph) if graph.communities.len() < 2 => {
+                issues.push(format!("The dialect map has {} communities; at least 2 are needed.", graph.communities.len()));
+            }
+            Ok(_) => {}
+            Err(e) => issues.push(e.to_string()),
+        }
+
+        let registers = &self.registers;
+        let weights: Vec<f64> = Register::ALL.iter().map(|r| registers.weights.of(*r)).collect();
+        if weights.iter().any(|w| *w < 0.0) || weights.iter().sum::<f64>() <= 0.0 {
+            issues.push("Register weights must be non-negative and not all zero.".to_string());
+        }
+        if !(0.0..=1.0).contains(&registers.slang_rate) {
+            issues.push(format!("Register slang_rate is {}, outside 0.0 - 1.0.", registers.slang_rate));
+        }
+        for change in &registers.archaisms {
+            if !inventory.phonemes().any(|p| p.grapheme == change.from) {
+                issues.push(format!("Archaism '{}' changes '{}', which is not in the phoneme inventory.", change.describe(), change.from));
+            }
+            if !self.lexicon_generation.sound_changes.iter().any(|s| s.from == change.to && s.to == change.from) {
+                issues.push(format!("Archaism '{}' undoes none of the lexicon's sound changes, so no word has an older form for it.", change.describe()));
+            }
+        }
+        for honorific in &registers.honorifics {
+            for pos in &honorific.applies_to_pos {
+                if !self.lexicon_generation.parts_of_speech.contains(pos) {
+                    issues.push(format!("Honorific '{}' applies to unknown part of speech '{}'.", honorific.process.affix(), pos));
+                }
+            }
+        }
+
+        let script = &self.script;
+        let strokes = &script.strokes;
+        let script_issues = issues.len();
+        if [strokes.line, strokes.arc, strokes.loop_, strokes.dot].iter().any(|w| *w < 0.0)
+            || strokes.line + strokes.arc + strokes.loop_ + strokes.dot <= 0.0 {
+            issues.push("Script stroke weights must be non-negative and not all zero.".to_string());
+        }
+        if script.grid < 2 {
+            issues.push(format!("Script grid must have at least 2 anchor points per side, but has {}.", script.grid));
+        }
+        if script.min_strokes == 0 || script.min_strokes > script.max_strokes {
+            issues.push(format!("Script strokes range {} - {} is empty or starts at zero.", script.min_strokes, script.max_strokes));
+        }
+        if script.kind == ScriptKind::Abugida && inventory.vowels.is_empty() {
+            issues.push("An abugida needs at least one vowel to serve as the inherent vowel.".to_string());
+        }
+        // The script is drawn from its seed, so drawing it here shows whether the strokes allow
+        // a distinct glyph for every sign.
+        if issues.len() == script_issues
+            && let Err(e) = self.generate_script(inventory) {
+            issues.push(format!("{}.", e));
+        }
+
+        // Only IPA and the romanizations a phoneme configures are checked; a built-in scheme
+        // merging two sounds (popular "c" and "k") is a known loss, not a mistake.
+        let configured = inventory.schemes().into_iter()
+            .filter(|s| *s == Scheme::Ipa || inventory.phonemes().any(|p| p.romanizations.contains_key(s.name())));
+        for scheme in configured {
+            for (a, b, spelling) in inventory.ambiguous_spellings(&scheme) {
+                issues.push(format!("Phonemes '{}' and '{}' are both spelled '{}' in the {} scheme, so it can't be converted back.", a, b, spelling, scheme.name()));
+            }
+        }
+        if self.speech.sample_rate < 8000 {
+            issues.push(format!("Speech sample_rate ({}) is below 8000 Hz, too low for intelligible speech.", self.speech.sample_rate));
+        }
+        if self.speech.pitch <= 0.0 || self.speech.rate <= 0.0 {
+            issues.push("Speech pitch and rate must both be greater than zero.".to_string());
+        }
+        for (grapheme, approximation) in &self.pronunciation.approximations {
+            if !inventory.phonemes().any(|p| p.grapheme() == grapheme) {
+                issues.push(format!("Pronunciation approximation for '{}' names a phoneme the inventory does not have.", grapheme));
+            }
+            if approximation.respelling.is_empty() {
+                issues.push(format!("Pronunciation approximation for '{}' has an empty respelling.", grapheme));
+            }
+        }
+        for phoneme in inventory.phonemes() {
+            for (name, spelling) in &phoneme.romanizations {
+                if spelling.is_empty() || name == "native" || name == "ipa" {
+                    issues.push(format!("Phoneme '{}' has an empty or reserved romanization '{}'.", phoneme.grapheme, name));
+                }
+            }
+        }
+
+        let placeholder = Regex::new(r"\{(\d+)\}").unwrap();
+        for template in &self.naming.templates {
+            let word_parts = template.parts.iter().filter(|p| matches!(p, NamePart::Word { .. } | NamePart::Name { .. })).count();
+            if word_parts == 0 {
+                issues.push(format!("Name template '{}' has no Word or Name parts, so every name would be identical.", template.name));
+            }
+            for part in &template.parts {
+                if let NamePart::Word { rule: Some(name), .. } = part
+                    && !rule_exists(name) {
+                    issues.push(format!("Name template '{}' uses unknown rule '{}'.", template.name, name));
+                }
+                if let NamePart::Name { kind, template: inner } = part
+                    && self.inner_name_templates(inventory, *kind, inner.as_deref()).is_empty() {
+                    let wanted = inner.clone().unwrap_or_else(|| format!("{:?}", kind));
+                    issues.push(format!("Name template '{}' needs a '{}' name, but no {:?} template by that name is free of Name parts.", template.name, wanted, kind));
+                }
+            }
+            if let Some(gloss) = &template.gloss_template {
+                for index in placeholder.captures_iter(gloss).filter_map(|c| c[1].parse::<usize>().ok()) {
+                    if index >= template.parts.len() {
+                        issues.push(format!("Name template '{}' refers to part {{{}}} in its gloss_template, but it has {} part(s).", template.name, index, template.parts.len()));
+                    }
+                }
+            }
+        }
+
+        let glosses: Vec<String> = self.concepts().into_iter().map(|(_, m)| m.gloss.clone()).collect();
+        for template in &self.texts.templates {
+            let clauses: Vec<_> = template.opening.iter().chain(&template.per_event).chain(&template.closing).collect();
+            if clauses.is_empty() {
+                issues.push(format!("Text template '{}' has no clauses.", template.name));
+            }
+            for slot in clauses.iter().flat_map(|c| [&c.subject, &c.verb, &c.object, &c.oblique]).flat_map(|r| r.split_whitespace()) {
+                let problem = if let Some(kind) = slot.strip_prefix('@') {
+                    let kind = kind.split('#').next().unwrap_or(kind);
+                    (!["person", "place", "polity", "clan"].contains(&kind)).then(|| format!("names unknown name kind '{}'", kind))
+                } else if let Some(field) = slot.strip_prefix('$') {
+                    (!EVENT_FIELDS.contains(&field)).then(|| format!("uses unknown event field '{}'", field))
+                } else if let Some(gloss) = slot.strip_prefix('=') {
+                    (!glosses.iter().any(|g| g == gloss)).then(|| format!("asks for '{}', which is not one of the language's meanings", gloss))
+                } else {
+                    (!pos_is_reachable(&slot.to_string())).then(|| format!("asks for part of speech '{}', which no word ever has", slot))
+                };
+                if let Some(problem) = problem {
+                    issues.push(format!("Text template '{}' {}.", template.name, problem));
+                }
+            }
+        }
+
+        issues
+    }
+}
@@ -0,0 +1,522 @@
+use crate::Meaning;
+use std::collections::HashMap;
+
+// Standard concept lists a config can pull in with `"meanings_from": "<name>"`.
+// Each entry is (gloss, part of speech, semantic tags). Verbs are given as bare stems
+// ("drink", not "to drink") to read well inside meaning templates.
+pub const BUILTIN_WORD_LISTS: &[&str] = &["swadesh-100", "swadesh-207", "leipzig-jakarta", "worldbuilding"];
+
+type Concept = (&'static str, &'static str, &'static [&'static str]);
+
+// Looks up a built-in list by name and groups it by part of speech, like `meanings` in the JSON.
+pub fn builtin_word_list(name: &str) -> Option<HashMap<String, Vec<Meaning>>> {
+    let concepts: Vec<&Concept> = match name {
+        "swadesh-207" => SWADESH_207.iter().collect(),
+        "swadesh-100" => SWADESH_207.iter().filter(|(gloss, _, _)| SWADESH_100.contains(gloss)).collect(),
+        "leipzig-jakarta" => LEIPZIG_JAKARTA.iter().collect(),
+        "worldbuilding" => WORLDBUILDING.iter().collect(),
+        _ => return None,
+    };
+
+    let mut meanings: HashMap<String, Vec<Meaning>> = HashMap::new();
+    for (gloss, pos, tags) in concepts {
+        meanings.entry(pos.to_string()).or_default().push(Meaning {
+            gloss: gloss.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        });
+    }
+    Some(meanings)
+}
+
+// Swadesh's 207-item list.
+const SWADESH_207: &[Concept] = &[
+    ("I", "pronoun", &["person"]),
+    ("you (singular)", "pronoun", &["person"]),
+    ("he", "pronoun", &["person"]),
+    ("we", "pronoun", &["person"]),
+    ("you (plural)", "pronoun", &["person"]),
+    ("they", "pronoun", &["person"]),
+    ("this", "determiner", &["deixis", "near"]),
+    ("that", "determiner", &["deixis", "far"]),
+    ("here", "adverb", &["deixis", "location", "near"]),
+    ("there", "adverb", &["deixis", "location", "far"]),
+    ("who", "pronoun", &["question"]),
+    ("what", "pronoun", &["question"]),
+    ("where", "adverb", &["question", "location"]),
+    ("when", "adverb", &["question", "time"]),
+    ("how", "adverb", &["question"]),
+    ("not", "particle", &["grammatical"]),
+    ("all", "determiner", &["quantity"]),
+    ("many", "determiner", &["quantity"]),
+    ("some", "determiner", &["quantity"]),
+    ("few", "determiner", &["quantity", "small"]),
+    ("other", "determiner", &["grammatical"]),
+    ("one", "numeral", &["number"]),
+    ("two", "numeral", &["number"]),
+    ("three", "numeral", &["number"]),
+    ("four", "numeral", &["number"]),
+    ("five", "numeral", &["number"]),
+    ("big", "adj", &["size", "big"]),
+    ("long", "adj", &["size", "big"]),
+    ("wide", "adj", &["size", "big"]),
+    ("thick", "adj", &["size", "big"]),
+    ("heavy", "adj", &["physical", "heavy"]),
+    ("small", "adj", &["size", "small"]),
+    ("short", "adj", &["size", "small"]),
+    ("narrow", "adj", &["size", "small"]),
+    ("thin", "adj", &["size", "small"]),
+    ("woman", "noun", &["concrete", "animate", "person"]),
+    ("man", "noun", &["concrete", "animate", "person"]),
+    ("person", "noun", &["concrete", "animate", "person"]),
+    ("child", "noun", &["concrete", "animate", "person", "kinship", "small"]),
+    ("wife", "noun", &["concrete", "animate", "person", "kinship"]),
+    ("husband", "noun", &["concrete", "animate", "person", "kinship"]),
+    ("mother", "noun", &["concrete", "animate", "person", "kinship"]),
+    ("father", "noun", &["concrete", "animate", "person", "kinship"]),
+    ("animal", "noun", &["concrete", "animate", "animal"]),
+    ("fish", "noun", &["concrete", "animate", "animal", "sea"]),
+    ("bird", "noun", &["concrete", "animate", "animal"]),
+    ("dog", "noun", &["concrete", "animate", "animal"]),
+    ("louse", "noun", &["concrete", "animate", "animal", "small"]),
+    ("snake", "noun", &["concrete", "animate", "animal"]),
+    ("worm", "noun", &["concrete", "animate", "animal", "small"]),
+    ("tree", "noun", &["concrete", "plant"]),
+    ("forest", "noun", &["concrete", "plant", "location", "landscape"]),
+    ("stick", "noun", &["concrete", "plant", "tool"]),
+    ("fruit", "noun", &["concrete", "plant", "food"]),
+    ("seed", "noun", &["concrete", "plant", "small"]),
+    ("leaf", "noun", &["concrete", "plant"]),
+    ("root", "noun", &["concrete", "plant"]),
+    ("bark", "noun", &["concrete", "plant"]),
+    ("flower", "noun", &["concrete", "plant"]),
+    ("grass", "noun", &["concrete", "plant", "mass"]),
+    ("rope", "noun", &["concrete", "tool"]),
+    ("skin", "noun", &["concrete", "body"]),
+    ("meat", "noun", &["concrete", "body", "food", "mass"]),
+    ("blood", "noun", &["concrete", "body", "mass"]),
+    ("bone", "noun", &["concrete", "body"]),
+    ("fat", "noun", &["concrete", "body", "mass"]),
+    ("egg", "noun", &["concrete", "food"]),
+    ("horn", "noun", &["concrete", "body", "animal"]),
+    ("tail", "noun", &["concrete", "body", "animal"]),
+    ("feather", "noun", &["concrete", "body", "animal", "small"]),
+    ("hair", "noun", &["concrete", "body"]),
+    ("head", "noun", &["concrete", "body"]),
+    ("ear", "noun", &["concrete", "body"]),
+    ("eye", "noun", &["concrete", "body"]),
+    ("nose", "noun", &["concrete", "body"]),
+    ("mouth", "noun", &["concrete", "body"]),
+    ("tooth", "noun", &["concrete", "body"]),
+    ("tongue", "noun", &["concrete", "body"]),
+    ("fingernail", "noun", &["concrete", "body", "small"]),
+    ("foot", "noun", &["concrete", "body"]),
+    ("leg", "noun", &["concrete", "body"]),
+    ("knee", "noun", &["concrete", "body"]),
+    ("hand", "noun", &["concrete", "body"]),
+    ("wing", "noun", &["concrete", "body", "animal"]),
+    ("belly", "noun", &["concrete", "body"]),
+    ("guts", "noun", &["concrete", "body"]),
+    ("neck", "noun", &["concrete", "body"]),
+    ("back", "noun", &["concrete", "body"]),
+    ("breast", "noun", &["concrete", "body"]),
+    ("heart", "noun", &["concrete", "body", "emotion"]),
+    ("liver", "noun", &["concrete", "body"]),
+    ("drink", "verb", &["action", "food"]),
+    ("eat", "verb", &["action", "food"]),
+    ("bite", "verb", &["action", "body"]),
+    ("suck", "verb", &["action", "body", "onomatopoeia"]),
+    ("spit", "verb", &["action", "body", "onomatopoeia"]),
+    ("vomit", "verb", &["action", "body"]),
+    ("blow", "verb", &["action", "onomatopoeia"]),
+    ("breathe", "verb", &["action", "body"]),
+    ("laugh", "verb", &["action", "emotion", "onomatopoeia"]),
+    ("see", "verb", &["perception"]),
+    ("hear", "verb", &["perception"]),
+    ("know", "verb", &["mental"]),
+    ("think", "verb", &["mental"]),
+    ("smell", "verb", &["perception"]),
+    ("fear", "verb", &["emotion"]),
+    ("sleep", "verb", &["state", "body"]),
+    ("live", "verb", &["state"]),
+    ("die", "verb", &["state", "death"]),
+    ("kill", "verb", &["action", "violence", "death"]),
+    ("fight", "verb", &["action", "violence", "war"]),
+    ("hunt", "verb", &["action", "animal"]),
+    ("hit", "verb", &["action", "violence", "onomatopoeia"]),
+    ("cut", "verb", &["action", "craft"]),
+    ("split", "verb", &["action", "craft"]),
+    ("stab", "verb", &["action", "violence"]),
+    ("scratch", "verb", &["action", "onomatopoeia"]),
+    ("dig", "verb", &["action", "craft"]),
+    ("swim", "verb", &["motion", "sea"]),
+    ("fly", "verb", &["motion"]),
+    ("walk", "verb", &["motion"]),
+    ("come", "verb", &["motion"]),
+    ("lie", "verb", &["state", "position"]),
+    ("sit", "verb", &["state", "position"]),
+    ("stand", "verb", &["state", "position"]),
+    ("turn", "verb", &["motion"]),
+    ("fall", "verb", &["motion"]),
+    ("give", "verb", &["action", "trade"]),
+    ("hold", "verb", &["action"]),
+    ("squeeze", "verb", &["action"]),
+    ("rub", "verb", &["action"]),
+    ("wash", "verb", &["action"]),
+    ("wipe", "verb", &["action"]),
+    ("pull", "verb", &["action"]),
+    ("push", "verb", &["action"]),
+    ("throw", "verb", &["action"]),
+    ("tie", "verb", &["action", "craft"]),
+    ("sew", "verb", &["action", "craft"]),
+    ("count", "verb", &["mental", "trade"]),
+    ("say", "verb", &["speech"]),
+    ("sing", "verb", &["speech"]),
+    ("play", "verb", &["action"]),
+    ("float", "verb", &["motion", "sea"]),
+    ("flow", "verb", &["motion", "water"]),
+    ("freeze", "verb", &["state", "weather"]),
+    ("swell", "verb", &["state"]),
+    ("sun", "noun", &["concrete", "sky", "bright"]),
+    ("moon", "noun", &["concrete", "sky", "bright"]),
+    ("star", "noun", &["concrete", "sky", "bright", "small"]),
+    ("water", "noun", &["concrete", "mass", "water"]),
+    ("rain", "noun", &["concrete", "mass", "weather", "water"]),
+    ("river", "noun", &["concrete", "water", "location", "landscape"]),
+    ("lake", "noun", &["concrete", "water", "location", "landscape"]),
+    ("sea", "noun", &["concrete", "water", "location", "landscape", "sea", "big"]),
+    ("salt", "noun", &["concrete", "mass", "food", "sea"]),
+    ("stone", "noun", &["concrete", "mass", "material", "heavy"]),
+    ("sand", "noun", &["concrete", "mass", "material"]),
+    ("dust", "noun", &["concrete", "mass", "small"]),
+    ("earth", "noun", &["concrete", "mass", "material", "landscape"]),
+    ("cloud", "noun", &["concrete", "sky", "weather"]),
+    ("fog", "noun", &["concrete", "mass", "weather", "dark"]),
+    ("sky", "noun", &["concrete", "sky", "location"]),
+    ("wind", "noun", &["concrete", "weather"]),
+    ("snow", "noun", &["concrete", "mass", "weather", "bright"]),
+    ("ice", "noun", &["concrete", "mass", "weather"]),
+    ("smoke", "noun", &["concrete", "mass", "fire", "dark"]),
+    ("fire", "noun", &["concrete", "fire", "bright"]),
+    ("ash", "noun", &["concrete", "mass", "fire"]),
+    ("burn", "verb", &["action", "fire"]),
+    ("road", "noun", &["concrete", "location", "trade"]),
+    ("mountain", "noun", &["concrete", "location", "landscape", "big"]),
+    ("red", "adj", &["colour"]),
+    ("green", "adj", &["colour"]),
+    ("yellow", "adj", &["colour", "bright"]),
+    ("white", "adj", &["colour", "bright"]),
+    ("black", "adj", &["colour", "dark"]),
+    ("night", "noun", &["abstract", "time", "dark"]),
+    ("day", "noun", &["abstract", "time", "bright"]),
+    ("year", "noun", &["abstract", "time"]),
+    ("warm", "adj", &["physical", "weather"]),
+    ("cold", "adj", &["physical", "weather"]),
+    ("full", "adj", &["physical", "quantity"]),
+    ("new", "adj", &["time"]),
+    ("old", "adj", &["time"]),
+    ("good", "adj", &["evaluation"]),
+    ("bad", "adj", &["evaluation"]),
+    ("rotten", "adj", &["physical", "evaluation"]),
+    ("dirty", "adj", &["physical", "evaluation"]),
+    ("straight", "adj", &["shape"]),
+    ("round", "adj", &["shape"]),
+    ("sharp", "adj", &["physical", "shape"]),
+    ("dull", "adj", &["physical", "shape"]),
+    ("smooth", "adj", &["physical"]),
+    ("wet", "adj", &["physical", "water"]),
+    ("dry", "adj", &["physical"]),
+    ("correct", "adj", &["evaluation"]),
+    ("near", "adj", &["location", "near"]),
+    ("far", "adj", &["location", "far"]),
+    ("right", "adj", &["location"]),
+    ("left", "adj", &["location"]),
+    ("at", "adposition", &["grammatical", "location"]),
+    ("in", "adposition", &["grammatical", "location"]),
+    ("with", "adposition", &["grammatical"]),
+    ("and", "conjunction", &["grammatical"]),
+    ("if", "conjunction", &["grammatical"]),
+    ("because", "conjunction", &["grammatical"]),
+    ("name", "noun", &["abstract", "speech"]),
+];
+
+// The glosses of SWADESH_207 that make up Swadesh's final 100-item list.
+const SWADESH_100: &[&str] = &[
+    "I", "you (singular)", "we", "this", "that", "who", "what", "not", "all", "many",
+    "one", "two", "big", "long", "small", "woman", "man", "person", "fish", "bird",
+    "dog", "louse", "tree", "seed", "leaf", "root", "bark", "skin", "meat", "blood",
+    "bone", "fat", "egg", "horn", "tail", "feather", "hair", "head", "ear", "eye",
+    "nose", "mouth", "tooth", "tongue", "fingernail", "foot", "knee", "hand", "belly", "neck",
+    "breast", "heart", "liver", "drink", "eat", "bite", "see", "hear", "know", "sleep",
+    "die", "kill", "swim", "fly", "walk", "come", "lie", "sit", "stand", "give",
+    "say", "sun", "moon", "star", "water", "rain", "stone", "sand", "earth", "cloud",
+    "smoke", "fire", "ash", "burn", "road", "mountain", "red", "green", "yellow", "white",
+    "black", "night", "warm", "cold", "full", "new", "good", "round", "dry", "name",
+];
+
+// The Leipzig-Jakarta list of the 100 concepts most resistant to borrowing (Tadmor 2009).
+const LEIPZIG_JAKARTA: &[Concept] = &[
+    ("fire", "noun", &["concrete", "fire", "bright"]),
+    ("nose", "noun", &["concrete", "body"]),
+    ("go", "verb", &["motion"]),
+    ("water", "noun", &["concrete", "mass", "water"]),
+    ("mouth", "noun", &["concrete", "body"]),
+    ("tongue", "noun", &["concrete", "body"]),
+    ("blood", "noun", &["concrete", "body", "mass"]),
+    ("bone", "noun", &["concrete", "body"]),
+    ("you (singular)", "pronoun", &["person"]),
+    ("root", "noun", &["concrete", "plant"]),
+    ("come", "verb", &["motion"]),
+    ("breast", "noun", &["concrete", "body"]),
+    ("rain", "noun", &["concrete", "mass", "weather", "water"]),
+    ("I", "pronoun", &["person"]),
+    ("name", "noun", &["abstract", "speech"]),
+    ("louse", "noun", &["concrete", "animate", "animal", "small"]),
+    ("wing", "noun", &["concrete", "body", "animal"]),
+    ("meat", "noun", &["concrete", "body", "food", "mass"]),
+    ("hand", "noun", &["concrete", "body"]),
+    ("fly", "noun", &["concrete", "animate", "animal", "small", "onomatopoeia"]),
+    ("night", "noun", &["abstract", "time", "dark"]),
+    ("ear", "noun", &["concrete", "body"]),
+    ("neck", "noun", &["concrete", "body"]),
+    ("far", "adj", &["location", "far"]),
+    ("make", "verb", &["action", "craft"]),
+    ("house", "noun", &["concrete", "location", "building"]),
+    ("stone", "noun", &["concrete", "mass", "material", "heavy"]),
+    ("bitter", "adj", &["physical", "food"]),
+    ("say", "verb", &["speech"]),
+    ("tooth", "noun", &["concrete", "body"]),
+    ("hair", "noun", &["concrete", "body"]),
+    ("big", "adj", &["size", "big"]),
+    ("one", "numeral", &["number"]),
+    ("who", "pronoun", &["question"]),
+    ("he", "pronoun", &["person"]),
+    ("hit", "verb", &["action", "violence", "onomatopoeia"]),
+    ("leg", "noun", &["concrete", "body"]),
+    ("horn", "noun", &["concrete", "body", "animal"]),
+    ("this", "determiner", &["deixis", "near"]),
+    ("fish", "noun", &["concrete", "animate", "animal", "sea"]),
+    ("yesterday", "adverb", &["time"]),
+    ("drink", "verb", &["action", "food"]),
+    ("black", "adj", &["colour", "dark"]),
+    ("navel", "noun", &["concrete", "body", "small"]),
+    ("stand", "verb", &["state", "position"]),
+    ("bite", "verb", &["action", "body"]),
+    ("back", "noun", &["concrete", "body"]),
+    ("wind", "noun", &["concrete", "weather"]),
+    ("smoke", "noun", &["concrete", "mass", "fire", "dark"]),
+    ("what", "pronoun", &["question"]),
+    ("child", "noun", &["concrete", "animate", "person", "kinship", "small"]),
+    ("egg", "noun", &["concrete", "food"]),
+    ("give", "verb", &["action", "trade"]),
+    ("new", "adj", &["time"]),
+    ("burn", "verb", &["action", "fire"]),
+    ("not", "particle", &["grammatical"]),
+    ("good", "adj", &["evaluation"]),
+    ("know", "verb", &["mental"]),
+    ("knee", "noun", &["concrete", "body"]),
+    ("sand", "noun", &["concrete", "mass", "material"]),
+    ("laugh", "verb", &["action", "emotion", "onomatopoeia"]),
+    ("hear", "verb", &["perception"]),
+    ("soft", "adj", &["physical"]),
+    ("take", "verb", &["action", "trade"]),
+    ("eat", "verb", &["action", "food"]),
+    ("ant", "noun", &["concrete", "animate", "animal", "small"]),
+    ("thigh", "noun", &["concrete", "body"]),
+    ("leaf", "noun", &["concrete", "plant"]),
+    ("see", "verb", &["perception"]),
+    ("grind", "verb", &["action", "craft", "food"]),
+    ("weep", "verb", &["action", "emotion", "onomatopoeia"]),
+    ("heavy", "adj", &["physical", "heavy"]),
+    ("tie", "verb", &["action", "craft"]),
+    ("blow", "verb", &["action", "onomatopoeia"]),
+    ("small", "adj", &["size", "small"]),
+    ("hide", "verb", &["action"]),
+    ("shadow", "noun", &["concrete", "dark"]),
+    ("suck", "verb", &["action", "body", "onomatopoeia"]),
+    ("ash", "noun", &["concrete", "mass", "fire"]),
+    ("tail", "noun", &["concrete", "body", "animal"]),
+    ("sit", "verb", &["state", "position"]),
+    ("hide (skin)", "noun", &["concrete", "body", "animal"]),
+    ("run", "verb", &["motion"]),
+    ("liver", "noun", &["concrete", "body"]),
+    ("carry", "verb", &["action", "trade"]),
+    ("fall", "verb", &["motion"]),
+    ("in", "adposition", &["grammatical", "location"]),
+    ("long", "adj", &["size", "big"]),
+    ("red", "adj", &["colour"]),
+    ("star", "noun", &["concrete", "sky", "bright", "small"]),
+    ("bird", "noun", &["concrete", "animate", "animal"]),
+    ("dog", "noun", &["concrete", "animate", "animal"]),
+    ("tree", "noun", &["concrete", "plant"]),
+    ("wood", "noun", &["concrete", "mass", "material", "plant"]),
+    ("skin", "noun", &["concrete", "body"]),
+    ("salt", "noun", &["concrete", "mass", "food", "sea"]),
+    ("sweet", "adj", &["physical", "food"]),
+    ("old", "adj", &["time"]),
+    ("wide", "adj", &["size", "big"]),
+    ("arm", "noun", &["concrete", "body"]),
+];
+
+// A thematic list of concepts fantasy settings reach for that the basic lists leave out.
+// The domain tags (war, craft, trade, religion, sea, ...) line up with cultural salience.
+const WORLDBUILDING: &[Concept] = &[
+    // Society and rule
+    ("king", "noun", &["concrete", "animate", "person", "society"]),
+    ("queen", "noun", &["concrete", "animate", "person", "society"]),
+    ("lord", "noun", &["concrete", "animate", "person", "society"]),
+    ("servant", "noun", &["concrete", "animate", "person", "society"]),
+    ("slave", "noun", &["concrete", "animate", "person", "society"]),
+    ("stranger", "noun", &["concrete", "animate", "person", "society"]),
+    ("guest", "noun", &["concrete", "animate", "person", "society"]),
+    ("clan", "noun", &["abstract", "society", "kinship"]),
+    ("ancestor", "noun", &["concrete", "animate", "person", "kinship", "religion"]),
+    ("son", "noun", &["concrete", "animate", "person", "kinship"]),
+    ("daughter", "noun", &["concrete", "animate", "person", "kinship"]),
+    ("brother", "noun", &["concrete", "animate", "person", "kinship"]),
+    ("sister", "noun", &["concrete", "animate", "person", "kinship"]),
+    ("law", "noun", &["abstract", "society"]),
+    ("oath", "noun", &["abstract", "society", "speech", "honour"]),
+    ("tribute", "noun", &["abstract", "society", "trade"]),
+    ("throne", "noun", &["concrete", "society", "location"]),
+    ("crown", "noun", &["concrete", "society", "metal"]),
+    ("rule", "verb", &["action", "society"]),
+    ("judge", "verb", &["action", "society", "mental"]),
+    ("swear", "verb", &["speech", "society", "honour"]),
+    ("betray", "verb", &["action", "society", "honour"]),
+    // War
+    ("war", "noun", &["abstract", "war", "violence"]),
+    ("battle", "noun", &["abstract", "war", "violence"]),
+    ("warrior", "noun", &["concrete", "animate", "person", "war"]),
+    ("enemy", "noun", &["concrete", "animate", "person", "war"]),
+    ("sword", "noun", &["concrete", "tool", "war", "metal"]),
+    ("spear", "noun", &["concrete", "tool", "war"]),
+    ("shield", "noun", &["concrete", "tool", "war"]),
+    ("bow", "noun", &["concrete", "tool", "war"]),
+    ("arrow", "noun", &["concrete", "tool", "war", "small"]),
+    ("helmet", "noun", &["concrete", "tool", "war", "metal"]),
+    ("fortress", "noun", &["concrete", "location", "building", "war", "big"]),
+    ("wall", "noun", &["concrete", "building", "war"]),
+    ("attack", "verb", &["action", "war", "violence"]),
+    ("defend", "verb", &["action", "war"]),
+    ("conquer", "verb", &["action", "war", "society"]),
+    ("avenge", "verb", &["action", "war", "violence", "honour"]),
+    // Craft and material
+    ("iron", "noun", &["concrete", "mass", "metal", "material", "heavy"]),
+    ("gold", "noun", &["concrete", "mass", "metal", "material", "trade", "bright"]),
+    ("silver", "noun", &["concrete", "mass", "metal", "material", "trade", "bright"]),
+    ("copper", "noun", &["concrete", "mass", "metal", "material"]),
+    ("gem", "noun", &["concrete", "material", "trade", "bright", "small"]),
+    ("ore", "noun", &["concrete", "mass", "material", "metal"]),
+    ("coal", "noun", &["concrete", "mass", "material", "fire", "dark"]),
+    ("hammer", "noun", &["concrete", "tool", "craft", "heavy"]),
+    ("anvil", "noun", &["concrete", "tool", "craft", "metal", "heavy"]),
+    ("axe", "noun", &["concrete", "tool", "craft", "war"]),
+    ("knife", "noun", &["concrete", "tool", "craft", "small"]),
+    ("wheel", "noun", &["concrete", "tool", "craft"]),
+    ("forge", "verb", &["action", "craft", "metal", "fire"]),
+    ("carve", "verb", &["action", "craft"]),
+    ("weave", "verb", &["action", "craft"]),
+    ("build", "verb", &["action", "craft", "building"]),
+    ("mine", "verb", &["action", "craft", "mountain"]),
+    // Trade
+    ("coin", "noun", &["concrete", "trade", "metal", "small"]),
+    ("price", "noun", &["abstract", "trade"]),
+    ("debt", "noun", &["abstract", "trade", "society"]),
+    ("merchant", "noun", &["concrete", "animate", "person", "trade"]),
+    ("market", "noun", &["concrete", "location", "trade"]),
+    ("buy", "verb", &["action", "trade"]),
+    ("sell", "verb", &["action", "trade"]),
+    ("owe", "verb", &["state", "trade", "society"]),
+    // Religion and the unseen
+    ("god", "noun", &["concrete", "animate", "religion"]),
+    ("spirit", "noun", &["abstract", "animate", "religion"]),
+    ("soul", "noun", &["abstract", "religion"]),
+    ("prayer", "noun", &["abstract", "religion", "speech"]),
+    ("temple", "noun", &["concrete", "location", "building", "religion"]),
+    ("priest", "noun", &["concrete", "animate", "person", "religion"]),
+    ("curse", "noun", &["abstract", "religion", "speech"]),
+    ("omen", "noun", &["abstract", "religion"]),
+    ("grave", "noun", &["concrete", "location", "death", "religion"]),
+    ("fate", "noun", &["abstract", "religion", "time"]),
+    ("pray", "verb", &["speech", "religion"]),
+    ("bless", "verb", &["speech", "religion"]),
+    ("sacrifice", "verb", &["action", "religion", "death"]),
+    // Landscape
+    ("valley", "noun", &["concrete", "location", "landscape"]),
+    ("cave", "noun", &["concrete", "location", "landscape", "mountain", "dark"]),
+    ("cliff", "noun", &["concrete", "location", "landscape", "mountain"]),
+    ("peak", "noun", &["concrete", "location", "landscape", "mountain"]),
+    ("hill", "noun", &["concrete", "location", "landscape"]),
+    ("desert", "noun", &["concrete", "location", "landscape", "desert"]),
+    ("dune", "noun", &["concrete", "location", "landscape", "desert"]),
+    ("oasis", "noun", &["concrete", "location", "landscape", "desert", "water"]),
+    ("coast", "noun", &["concrete", "location", "landscape", "sea"]),
+    ("island", "noun", &["concrete", "location", "landscape", "sea"]),
+    ("harbour", "noun", &["concrete", "location", "sea", "trade"]),
+    ("wave", "noun", &["concrete", "water", "sea"]),
+    ("tide", "noun", &["concrete", "water", "sea", "time"]),
+    ("ford", "noun", &["concrete", "location", "water"]),
+    ("swamp", "noun", &["concrete", "location", "landscape", "water"]),
+    ("ship", "noun", &["concrete", "tool", "sea", "trade", "big"]),
+    ("oar", "noun", &["concrete", "tool", "sea"]),
+    ("sail", "verb", &["motion", "sea"]),
+    ("town", "noun", &["concrete", "location", "building", "society"]),
+    ("hall", "noun", &["concrete", "location", "building", "society"]),
+    ("gate", "noun", &["concrete", "building"]),
+    ("tower", "noun", &["concrete", "building", "big"]),
+    // Weather and time
+    ("storm", "noun", &["concrete", "weather", "violence", "onomatopoeia"]),
+    ("thunder", "noun", &["concrete", "weather", "onomatopoeia", "big"]),
+    ("lightning", "noun", &["concrete", "weather", "bright"]),
+    ("winter", "noun", &["abstract", "time", "weather"]),
+    ("summer", "noun", &["abstract", "time", "weather"]),
+    ("dawn", "noun", &["abstract", "time", "bright"]),
+    ("dusk", "noun", &["abstract", "time", "dark"]),
+    ("age", "noun", &["abstract", "time"]),
+    // Animals and food
+    ("horse", "noun", &["concrete", "animate", "animal", "big"]),
+    ("wolf", "noun", &["concrete", "animate", "animal"]),
+    ("bear", "noun", &["concrete", "animate", "animal", "big", "heavy"]),
+    ("eagle", "noun", &["concrete", "animate", "animal"]),
+    ("raven", "noun", &["concrete", "animate", "animal", "dark", "onomatopoeia"]),
+    ("boar", "noun", &["concrete", "animate", "animal"]),
+    ("goat", "noun", &["concrete", "animate", "animal"]),
+    ("cattle", "noun", &["concrete", "animate", "animal", "mass", "trade"]),
+    ("bee", "noun", &["concrete", "animate", "animal", "small", "onomatopoeia"]),
+    ("bread", "noun", &["concrete", "mass", "food"]),
+    ("grain", "noun", &["concrete", "mass", "food", "plant"]),
+    ("beer", "noun", &["concrete", "mass", "food"]),
+    ("mead", "noun", &["concrete", "mass", "food"]),
+    ("feast", "noun", &["abstract", "food", "society"]),
+    // Mind and feeling
+    ("honour", "noun", &["abstract", "honour", "society"]),
+    ("shame", "noun", &["abstract", "honour", "emotion"]),
+    ("grudge", "noun", &["abstract", "emotion", "honour"]),
+    ("anger", "noun", &["abstract", "emotion"]),
+    ("joy", "noun", &["abstract", "emotion"]),
+    ("grief", "noun", &["abstract", "emotion", "death"]),
+    ("courage", "noun", &["abstract", "emotion", "war"]),
+    ("wisdom", "noun", &["abstract", "mental"]),
+    ("memory", "noun", &["abstract", "mental", "time"]),
+    ("song", "noun", &["abstract", "speech"]),
+    ("story", "noun", &["abstract", "speech"]),
+    ("word", "noun", &["abstract", "speech"]),
+    ("remember", "verb", &["mental", "time"]),
+    ("forget", "verb", &["mental", "time"]),
+    ("endure", "verb", &["state"]),
+    ("forgive", "verb", &["action", "emotion", "honour"]),
+    ("mourn", "verb", &["emotion", "death"]),
+    ("brave", "adj", &["evaluation", "war", "honour"]),
+    ("proud", "adj", &["evaluation", "honour"]),
+    ("holy", "adj", &["evaluation", "religion"]),
+    ("cursed", "adj", &["evaluation", "religion"]),
+    ("rich", "adj", &["evaluation", "trade"]),
+    ("poor", "adj", &["evaluation", "trade"]),
+    ("deep", "adj", &["physical", "size"]),
+    ("hard", "adj", &["physical"]),
+    ("strong", "adj", &["physical", "war"]),
+    ("dark", "adj", &["colour", "dark"]),
+    ("bright", "adj", &["colour", "bright"]),
+    ("tiny", "adj", &["size", "small"]),
+    ("vast", "adj", &["size", "big"]),
+];